@@ -0,0 +1,135 @@
+use super::{plane::Vector, polygon::Polygon};
+
+/// Distance from a splitting plane under which a vertex is considered lying on it.
+const TOLERANCE: f64 = 1e-9;
+
+/// Maximum number of candidate splitting planes evaluated at each node.
+const CANDIDATES: usize = 16;
+
+/// Node of a binary space partitioning tree.
+pub struct BspNode {
+    /// Splitting plane `normal · p = d` represented by its unit normal and offset.
+    pub plane: (Vector, f64),
+    /// Subtree of the polygons lying in front of the plane, namely where `normal · p > d`.
+    pub front: Option<Box<BspNode>>,
+    /// Subtree of the polygons lying behind the plane, namely where `normal · p < d`.
+    pub back: Option<Box<BspNode>>,
+    /// Polygons lying on the splitting plane.
+    pub polygons: Vec<Polygon>,
+}
+
+/// The position of a polygon with respect to a splitting plane.
+enum Side {
+    /// All vertices lie on the plane.
+    Coplanar,
+    /// All vertices lie in front of the plane or on it.
+    Front,
+    /// All vertices lie behind the plane or on it.
+    Back,
+    /// Some vertices lie in front of the plane and others behind it.
+    Spanning,
+}
+
+/// Classifies the position of `polygon` with respect to the splitting `plane`.
+fn classify(polygon: &Polygon, (normal, d): &(Vector, f64)) -> Side {
+    let (mut front, mut back) = (false, false);
+    // checks on which side each vertex lies
+    for vertex in polygon.iter() {
+        let distance = normal.dot(&Vector::from(&vertex)) - d;
+
+        if distance > TOLERANCE {
+            front = true;
+        } else if distance < -TOLERANCE {
+            back = true;
+        }
+    }
+
+    match (front, back) {
+        (false, false) => Side::Coplanar,
+        (true, false) => Side::Front,
+        (false, true) => Side::Back,
+        (true, true) => Side::Spanning,
+    }
+}
+
+/// Computes the plane supporting `polygon` unless it is degenerate.
+fn support(polygon: &Polygon) -> Option<(Vector, f64)> {
+    // unit normal of the polygon's plane
+    let normal = polygon.normal().normalize();
+    // a zero normal means that the polygon does not describe any plane
+    if normal.norm() <= f64::EPSILON {
+        None
+    } else {
+        Some((normal, normal.dot(&Vector::from(&polygon.centroid()))))
+    }
+}
+
+/// Selects the splitting plane among those supporting a sample of `polygons` by minimizing the number of
+/// required splits and the imbalance between the two subtrees.
+fn select(polygons: &[Polygon]) -> Option<(Vector, f64)> {
+    // evenly samples the candidates to bound the cost of the selection
+    let step = polygons.len().div_ceil(CANDIDATES).max(1);
+
+    polygons
+        .iter()
+        .step_by(step)
+        .filter_map(support)
+        .map(|plane| {
+            let (mut splits, mut front, mut back) = (0usize, 0usize, 0usize);
+            // counts how the polygons would be distributed by the candidate plane
+            for polygon in polygons {
+                match classify(polygon, &plane) {
+                    Side::Coplanar => {}
+                    Side::Front => front += 1,
+                    Side::Back => back += 1,
+                    Side::Spanning => splits += 1,
+                }
+            }
+            // splits are heavily penalized because they increase the number of polygons
+            (plane, 8 * splits + front.abs_diff(back))
+        })
+        .min_by_key(|&(_, cost)| cost)
+        .map(|(plane, _)| plane)
+}
+
+/// Constructs a binary space partitioning tree from `polygons`.
+///
+/// At each node, the splitting plane is automatically selected among the planes supporting the polygons, then the
+/// polygons crossing it are split in two parts. When no polygon describes a proper plane, all of them are stored in
+/// a leaf whose plane has a zero normal.
+pub fn build(polygons: Vec<Polygon>) -> BspNode {
+    // without a splitting plane the node is necessarily a leaf
+    let Some(plane) = select(&polygons) else {
+        return BspNode {
+            plane: (Vector::zero(), 0f64),
+            front: None,
+            back: None,
+            polygons,
+        };
+    };
+    // partitions the polygons with respect to the splitting plane
+    let mut coplanar = Vec::<Polygon>::new();
+    let mut front = Vec::<Polygon>::new();
+    let mut back = Vec::<Polygon>::new();
+
+    for polygon in polygons {
+        match classify(&polygon, &plane) {
+            Side::Coplanar => coplanar.push(polygon),
+            Side::Front => front.push(polygon),
+            Side::Back => back.push(polygon),
+            Side::Spanning => {
+                // crossing polygons are split and each part is assigned to its own side
+                let (ahead, behind) = polygon.split_by_plane(&plane.0, plane.1, TOLERANCE);
+                front.extend(ahead);
+                back.extend(behind);
+            }
+        }
+    }
+    // recursively constructs the two subtrees
+    BspNode {
+        plane,
+        front: (!front.is_empty()).then(|| Box::new(build(front))),
+        back: (!back.is_empty()).then(|| Box::new(build(back))),
+        polygons: coplanar,
+    }
+}
@@ -0,0 +1,101 @@
+use super::polygon::Polygon;
+
+/// The settings of the construction of polygons from segments, see [crate::polygonalize_with_config].
+///
+/// The default configuration processes the connected components in parallel and discards the polygons whose area
+/// projected on the xy plane is less than `0.01`, as suggested for [crate::polygonalize].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolygonalizeConfig {
+    /// Whether the connected components are processed in parallel.
+    pub(super) parallelize: bool,
+    /// The minimum area of the polygons projected on the xy plane.
+    pub(super) minimum_area: f64,
+    /// The maximum area of the polygons projected on the xy plane.
+    pub(super) maximum_area: f64,
+    /// The minimum number of distinct vertices of the polygons.
+    pub(super) minimum_vertices: usize,
+    /// The optional maximum number of distinct vertices of the polygons.
+    pub(super) maximum_vertices: Option<usize>,
+    /// The distance under which the points of the segments are merged.
+    pub(super) snap_tolerance: f64,
+    /// The maximum number of segments of a path when traversing the graph.
+    pub(super) max_depth: usize,
+}
+
+impl Default for PolygonalizeConfig {
+    fn default() -> Self {
+        Self {
+            parallelize: true,
+            minimum_area: 0.01,
+            maximum_area: f64::INFINITY,
+            minimum_vertices: 3,
+            maximum_vertices: None,
+            snap_tolerance: 0f64,
+            max_depth: usize::MAX,
+        }
+    }
+}
+
+impl PolygonalizeConfig {
+    /// Sets whether the connected components are processed in parallel.
+    pub fn parallelize(mut self, parallelize: bool) -> Self {
+        self.parallelize = parallelize;
+        self
+    }
+
+    /// Discards the polygons whose area projected on the xy plane is less than `area`.
+    pub fn minimum_area(mut self, area: f64) -> Self {
+        self.minimum_area = area;
+        self
+    }
+
+    /// Discards the polygons whose area projected on the xy plane is greater than `area`.
+    pub fn maximum_area(mut self, area: f64) -> Self {
+        self.maximum_area = area;
+        self
+    }
+
+    /// Discards the polygons with less than `count` distinct vertices.
+    pub fn minimum_vertices(mut self, count: usize) -> Self {
+        self.minimum_vertices = count;
+        self
+    }
+
+    /// Discards the polygons with more than `count` distinct vertices, which often are artifacts of the data,
+    /// while by default there is no limit.
+    pub fn maximum_vertices(mut self, count: usize) -> Self {
+        self.maximum_vertices = Some(count);
+        self
+    }
+
+    /// Merges the points of the segments lying within `tolerance` of each other before pruning the graph, see
+    /// [crate::PointGraph::snap_close_vertices], while a zero tolerance disables snapping.
+    ///
+    /// Note that snapping is not merely cosmetic since it changes the connections of the graph and thus which
+    /// polygons are found: segments whose endpoints should coincide but differ by measurement noise are joined
+    /// together, whereas too large a tolerance might collapse small polygons.
+    pub fn snap_tolerance(mut self, tolerance: f64) -> Self {
+        self.snap_tolerance = tolerance;
+        self
+    }
+
+    /// Abandons the paths holding `depth` segments when traversing the graph, which bounds the depth of the recursion
+    /// and thus prevents overflowing the stack on very large graphs, while by default there is no limit.
+    ///
+    /// Note that the polygons with more than `depth` sides are never closed and thus silently missed, hence setting
+    /// `depth` too small discards valid large polygons.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Checks whether `polygon` satisfies the constraints other than the minimum area, which is meant as predicate of
+    /// [crate::polygon::filter_with] such that the polygons are discarded before selecting them by containment.
+    pub(super) fn accepts(&self, polygon: &Polygon) -> bool {
+        polygon.area_projected() <= self.maximum_area
+            && polygon.vertex_count() >= self.minimum_vertices
+            && self
+                .maximum_vertices
+                .is_none_or(|count| polygon.vertex_count() <= count)
+    }
+}
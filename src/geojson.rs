@@ -0,0 +1,33 @@
+use super::polygon::Polygon;
+
+impl Polygon {
+    /// Serializes the polygon as a GeoJSON `Polygon` geometry made of a single closed ring whose positions are
+    /// written as `[x, y, z]`.
+    pub fn to_geojson_geometry(&self) -> String {
+        format!(
+            "{{\"type\":\"Polygon\",\"coordinates\":[[{}]]}}",
+            self.iter()
+                .map(|vertex| format!("[{:?},{:?},{:?}]", vertex.x, vertex.y, vertex.z))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// Serializes `polygons` as a GeoJSON `FeatureCollection` where each feature wraps the geometry of a polygon
+/// without any property.
+pub fn polygons_to_geojson_feature_collection(polygons: &[Polygon]) -> String {
+    format!(
+        "{{\"type\":\"FeatureCollection\",\"features\":[{}]}}",
+        polygons
+            .iter()
+            .map(|polygon| {
+                format!(
+                    "{{\"type\":\"Feature\",\"properties\":{{}},\"geometry\":{}}}",
+                    polygon.to_geojson_geometry()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    )
+}
@@ -3,45 +3,284 @@ use super::point::{Point, Segment};
 use hashbrown::{HashMap, HashSet};
 use std::collections::{BTreeMap, BTreeSet};
 
-pub(super) struct PointGraph {
+/// This graph contains the points connected by the segments.
+#[derive(Clone, PartialEq)]
+pub struct PointGraph {
     /// The adjacency list that represents the graph of points.
     pub(super) adjacencies: HashMap<Point, HashSet<Point>>,
+    /// The optional weights of the segments, stored in both orientations.
+    pub(super) weights: HashMap<Segment, f32>,
 }
 
-pub(super) struct PointSubGraph<'a> {
-    /// Reference to the main graph
-    pub(super) graph: &'a PointGraph,
-    pub(super) points: Option<HashSet<Point>>,
+/// Summary of the complexity of a graph of points after pruning.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphStats {
+    /// The number of points left after pruning.
+    pub node_count: usize,
+    /// The number of undirected segments left after pruning.
+    pub edge_count: usize,
+    /// The number of connected components left after pruning.
+    pub component_count: usize,
+    /// The greatest number of segments sharing the same point.
+    pub max_degree: usize,
+    /// The average number of segments sharing a point, which is `0` for an empty graph.
+    pub avg_degree: f64,
+    /// The number of points removed by pruning.
+    pub pruned_node_count: usize,
 }
 
-impl PointGraph {
-    /// Given a list of segments, it constructs the graph of all detected and connected points.
-    pub(super) fn from(segments: &[Segment]) -> Self {
-        // empty adjacency list of points
-        let mut adjacencies = HashMap::<Point, HashSet<Point>>::new();
+/// The inconsistencies that can be detected in a graph of points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphError {
+    /// The point is connected to some other point but it is missing from the graph.
+    MissingNode(Point),
+    /// The point is connected to itself.
+    SelfLoop(Point),
+    /// The first point is connected to the second but not the other way around.
+    AsymmetricEdge(Point, Point),
+    /// The segment between the two points is given a weight which is not finite or not positive.
+    InvalidWeight(Point, Point, f32),
+}
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::MissingNode(point) => write!(f, "point {} is connected but missing", point),
+            GraphError::SelfLoop(point) => write!(f, "point {} is connected to itself", point),
+            GraphError::AsymmetricEdge(from, to) => {
+                write!(
+                    f,
+                    "point {} is connected to {} but not vice versa",
+                    from, to
+                )
+            }
+            GraphError::InvalidWeight(from, to, weight) => {
+                write!(
+                    f,
+                    "segment {} -> {} has weight {} which is not finite and positive",
+                    from, to, weight
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+impl Extend<Segment> for PointGraph {
+    /// Adds the `segments` to the graph as unweighted edges, such that it can be constructed incrementally.
+    fn extend<I: IntoIterator<Item = Segment>>(&mut self, segments: I) {
         // iterates over every segment
-        segments.iter().for_each(|&(u, v)| {
+        segments.into_iter().for_each(|(u, v)| {
             // adds the segment to the graph as an edge between the two points
-            adjacencies
+            self.adjacencies
                 .entry(u)
                 .and_modify(|to| {
                     to.insert(v);
                 })
                 .or_insert(HashSet::from([v]));
             // does the same for its flipped counterpart
-            adjacencies
+            self.adjacencies
                 .entry(v)
                 .and_modify(|to| {
                     to.insert(u);
                 })
                 .or_insert(HashSet::from([u]));
         });
+    }
+}
+
+impl FromIterator<Segment> for PointGraph {
+    /// Constructs the graph of the collected `segments`, see [PointGraph::from].
+    fn from_iter<I: IntoIterator<Item = Segment>>(segments: I) -> Self {
+        Self::from(&segments.into_iter().collect::<Vec<Segment>>())
+    }
+}
+
+pub(super) struct PointSubGraph<'a> {
+    /// Reference to the main graph
+    pub(super) graph: &'a PointGraph,
+    pub(super) points: Option<HashSet<Point>>,
+}
+
+impl PointGraph {
+    /// Given a list of segments, it constructs the graph of all detected and connected points.
+    pub fn from(segments: &[Segment]) -> Self {
+        // empty adjacency list of points
+        let mut graph = Self {
+            adjacencies: HashMap::new(),
+            weights: HashMap::new(),
+        };
+        // adds every segment to the graph
+        graph.extend(segments.iter().copied());
         // yields the constructed graph of points
-        Self { adjacencies }
+        graph
+    }
+
+    /// Like [PointGraph::from] but the points lying within `tolerance` of an earlier point of `segments` are
+    /// identified with it while constructing the graph, such that vertices differing by rounding errors are shared.
+    ///
+    /// Unlike [PointGraph::snap_close_vertices], the points are not moved to a centroid but keep the location of the
+    /// first point of their group, found through a grid whose cells are as large as `tolerance`. Segments collapsing
+    /// into a single point are discarded.
+    pub fn from_with_tolerance(segments: &[Segment], tolerance: f64) -> Self {
+        if tolerance <= 0f64 {
+            return Self::from(segments);
+        }
+        let mut grid = HashMap::<Cell, Vec<Point>>::new();
+        // identifies a point with the first close one, otherwise it becomes a representative itself
+        let mut identify = |point: Point| {
+            let cell = cell(&point, tolerance);
+            // close points can only lie in the neighboring cells
+            let representative = neighboring_cells(cell)
+                .flat_map(|neighbor| grid.get(&neighbor).into_iter().flatten())
+                .find(|other| point.distance(other) <= tolerance)
+                .copied();
+
+            representative.unwrap_or_else(|| {
+                grid.entry(cell).or_default().push(point);
+                point
+            })
+        };
+
+        segments
+            .iter()
+            .map(|&(u, v)| (identify(u), identify(v)))
+            .filter(|(u, v)| u != v)
+            .collect()
+    }
+
+    /// Like [PointGraph::from] but also attaches to each segment a positive weight which expresses its reliability.
+    ///
+    /// When the same segment is given more than once, the last weight is kept. Since the election strategies divide
+    /// by the weights, [GraphError::InvalidWeight] is returned for the first one which is not finite and positive.
+    pub fn weighted(segments: &[(Segment, f32)]) -> Result<Self, GraphError> {
+        Self::validate_weights(segments)?;
+        // constructs the graph from the segments first
+        let mut graph = Self::from(
+            &segments
+                .iter()
+                .map(|&(segment, _)| segment)
+                .collect::<Vec<Segment>>(),
+        );
+        // then stores the weights regardless of the segments' orientation
+        segments.iter().for_each(|&((u, v), weight)| {
+            graph.weights.insert((u, v), weight);
+            graph.weights.insert((v, u), weight);
+        });
+
+        Ok(graph)
+    }
+
+    /// Checks that every weight is finite and positive, see [PointGraph::weighted].
+    pub(super) fn validate_weights(segments: &[(Segment, f32)]) -> Result<(), GraphError> {
+        match segments
+            .iter()
+            .find(|&&(_, weight)| !(weight.is_finite() && weight > 0f32))
+        {
+            Some(&((u, v), weight)) => Err(GraphError::InvalidWeight(u, v, weight)),
+            None => Ok(()),
+        }
+    }
+
+    /// Constructs a new graph where the points lying within `tolerance` of each other, also transitively, are merged
+    /// into their centroid.
+    ///
+    /// Close points are found through a grid whose cells are as large as `tolerance`, and segments collapsing into
+    /// a single point are discarded.
+    pub fn snap_close_vertices(&self, tolerance: f64) -> PointGraph {
+        if tolerance <= 0f64 {
+            return self.clone();
+        }
+
+        self.snapped(&self.snapping(tolerance))
+    }
+
+    /// Maps each point to the centroid of the group of points lying within `tolerance` of each other, also
+    /// transitively, see [PointGraph::snap_close_vertices].
+    pub(super) fn snapping(&self, tolerance: f64) -> HashMap<Point, Point> {
+        // sorts the points to make the output deterministic
+        let points = self
+            .adjacencies
+            .keys()
+            .copied()
+            .collect::<BTreeSet<Point>>()
+            .into_iter()
+            .collect::<Vec<Point>>();
+        let mut grid = HashMap::<Cell, Vec<usize>>::new();
+        // the groups of points as a disjoint set forest where each point links towards its representative
+        let mut parents = (0..points.len()).collect::<Vec<usize>>();
+        /// Finds the representative of the group of `index` while compressing the path towards it.
+        fn root(parents: &mut [usize], mut index: usize) -> usize {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        }
+
+        for (index, point) in points.iter().enumerate() {
+            let cell = cell(point, tolerance);
+            // close points can only lie in the neighboring cells
+            for neighbor in neighboring_cells(cell) {
+                for &other in grid.get(&neighbor).into_iter().flatten() {
+                    if point.distance(&points[other]) <= tolerance {
+                        let (a, b) = (root(&mut parents, index), root(&mut parents, other));
+                        parents[a] = b;
+                    }
+                }
+            }
+
+            grid.entry(cell).or_default().push(index);
+        }
+        // gathers the points of each group
+        let mut groups = HashMap::<usize, Vec<usize>>::new();
+        for index in 0..points.len() {
+            groups
+                .entry(root(&mut parents, index))
+                .or_default()
+                .push(index);
+        }
+        // replaces each point with the centroid of its group
+        let mut snapped = HashMap::<Point, Point>::new();
+        for members in groups.values() {
+            let count = members.len() as f64;
+            let centroid = Point {
+                x: members.iter().map(|&index| points[index].x).sum::<f64>() / count,
+                y: members.iter().map(|&index| points[index].y).sum::<f64>() / count,
+                z: members.iter().map(|&index| points[index].z).sum::<f64>() / count,
+            };
+
+            snapped.extend(members.iter().map(|&index| (points[index], centroid)));
+        }
+
+        snapped
+    }
+
+    /// Constructs a new graph where each point is replaced according to `snapped`, which must map every point of the
+    /// graph, while the segments collapsing into a single point are discarded.
+    pub(super) fn snapped(&self, snapped: &HashMap<Point, Point>) -> PointGraph {
+        // re-indexes the connections while discarding collapsed segments
+        let segments = self
+            .adjacencies
+            .iter()
+            .flat_map(|(&from, neighbors)| neighbors.iter().map(move |&to| (from, to)))
+            .map(|(from, to)| (snapped[&from], snapped[&to]))
+            .filter(|(from, to)| from != to)
+            .collect::<Vec<Segment>>();
+        let mut graph = Self::from(&segments);
+        graph.weights = self
+            .weights
+            .iter()
+            .map(|((from, to), &weight)| ((snapped[from], snapped[to]), weight))
+            .filter(|((from, to), _)| from != to)
+            .collect();
+
+        graph
     }
 
     /// Prunes the graph of points in-place by removing dead ends and related points and interconnections.
-    pub(super) fn prune(mut self) -> Self {
+    pub fn prune(mut self) -> Self {
         // detects the points which are dead ends and have degree equals to 1
         let mut leaves = self
             .adjacencies
@@ -79,8 +318,155 @@ impl PointGraph {
         self
     }
 
+    /// Checks the consistency of the graph and reports all its inconsistencies, sorted by point.
+    pub fn validate(&self) -> Result<(), Vec<GraphError>> {
+        let mut errors = Vec::<GraphError>::new();
+        // sorts the points to make the report deterministic
+        let points = self
+            .adjacencies
+            .keys()
+            .copied()
+            .collect::<BTreeSet<Point>>();
+
+        for point in &points {
+            for neighbor in self.adjacencies[point].iter().collect::<BTreeSet<_>>() {
+                if neighbor == point {
+                    errors.push(GraphError::SelfLoop(*point));
+                } else if let Some(neighbors) = self.adjacencies.get(neighbor) {
+                    // each connection must be stored in both directions
+                    if !neighbors.contains(point) {
+                        errors.push(GraphError::AsymmetricEdge(*point, *neighbor));
+                    }
+                } else {
+                    errors.push(GraphError::MissingNode(*neighbor));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Renders the graph in DOT language where each node is labelled with the coordinates of its point, written with
+    /// `precision` decimal places, and each connection is an undirected edge.
+    pub fn to_dot_with_coordinates(&self, precision: usize) -> String {
+        // sorts the points to make the output deterministic
+        let points = self
+            .adjacencies
+            .keys()
+            .copied()
+            .collect::<BTreeSet<Point>>();
+        // assigns an identifier to each point according to the ordering
+        let identifiers = points
+            .iter()
+            .enumerate()
+            .map(|(identifier, &point)| (point, identifier))
+            .collect::<HashMap<Point, usize>>();
+        // declares the nodes with their labels
+        let nodes = points.iter().map(|point| {
+            format!(
+                "    {} [label=\"{:.precision$},{:.precision$},{:.precision$}\"];\n",
+                identifiers[point], point.x, point.y, point.z
+            )
+        });
+        // declares each undirected edge once, from the lower to the greater point
+        let edges = points.iter().flat_map(|point| {
+            self.adjacencies[point]
+                .iter()
+                .filter(move |&neighbor| point < neighbor)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(|neighbor| {
+                    format!("    {} -- {};\n", identifiers[point], identifiers[neighbor])
+                })
+        });
+
+        format!("graph {{\n{}}}\n", nodes.chain(edges).collect::<String>())
+    }
+
+    /// Summarizes the graph, assuming it was already pruned of `pruned_node_count` points.
+    pub(super) fn stats(&self, pruned_node_count: usize) -> GraphStats {
+        // sums and maximizes the degrees in a single pass over the adjacency list
+        let (degrees, max_degree) = self
+            .adjacencies
+            .values()
+            .fold((0usize, 0usize), |(sum, max), neighbors| {
+                (sum + neighbors.len(), max.max(neighbors.len()))
+            });
+        let node_count = self.adjacencies.len();
+
+        GraphStats {
+            node_count,
+            // each segment is counted once from both its endpoints
+            edge_count: degrees / 2,
+            component_count: self.connected_component_count(),
+            max_degree,
+            avg_degree: if node_count > 0 {
+                degrees as f64 / node_count as f64
+            } else {
+                0f64
+            },
+            pruned_node_count,
+        }
+    }
+
+    /// Lazily detects the connected components of the graph, each one given as the set of its points.
+    pub fn connected_components(&self) -> impl Iterator<Item = HashSet<Point>> + '_ {
+        // explored vertices when identifying connected components
+        let mut explored = HashSet::<Point>::new();
+
+        self.adjacencies.keys().filter_map(move |point| {
+            // if the point has not been visited yet it will detect its associated connected component
+            if !explored.contains(point) {
+                let mut points = HashSet::<Point>::new();
+                // exploration as depth first traversal
+                self.explore(point, &mut explored, &mut points);
+                // returns the list of points as a connected component
+                Some(points)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Counts the connected components of the graph through a single depth first search over all points.
+    pub fn connected_component_count(&self) -> usize {
+        self.connected_components().count()
+    }
+
+    /// Performs a depth first search from node `point` to detect all points in connected component `partition`.
+    ///
+    /// The search relies on an explicit stack rather than recursion so that long chains of points cannot overflow
+    /// the call stack.
+    fn explore(
+        &self,
+        point: &Point,
+        explored: &mut HashSet<Point>,
+        partition: &mut HashSet<Point>,
+    ) {
+        // points waiting to be visited
+        let mut stack = vec![*point];
+
+        while let Some(point) = stack.pop() {
+            // visit only if not visited already
+            if explored.insert(point) {
+                // point is added to the connected component
+                partition.insert(point);
+                // its neighboring points are visited next
+                stack.extend(
+                    self.adjacencies[&point]
+                        .iter()
+                        .filter(|neighbor| !explored.contains(*neighbor)),
+                );
+            }
+        }
+    }
+
     /// Constructs a slice of the graph based on a set of its points.
-    pub(super) fn subgraph(&self, points: HashSet<Point>) -> PointSubGraph {
+    pub(super) fn subgraph(&self, points: HashSet<Point>) -> PointSubGraph<'_> {
         PointSubGraph {
             graph: self,
             points: Some(points),
@@ -88,7 +474,7 @@ impl PointGraph {
     }
 
     /// Constructs a slice of the graph with all points.
-    pub(super) fn fullgraph(&self) -> PointSubGraph {
+    pub(super) fn fullgraph(&self) -> PointSubGraph<'_> {
         PointSubGraph {
             graph: self,
             points: None,
@@ -96,10 +482,43 @@ impl PointGraph {
     }
 }
 
+/// Cell of the grid used to find the points close to each other.
+type Cell = (i64, i64, i64);
+
+/// Yields the cell containing `point` within the grid whose cells are as large as `size`.
+///
+/// The coordinates of the cell saturate to the bounds of `i64` when the point lies too far from the origin with
+/// respect to `size`, in which case the cell gathers far apart points which are told apart by their distance.
+fn cell(point: &Point, size: f64) -> Cell {
+    (
+        (point.x / size).floor() as i64,
+        (point.y / size).floor() as i64,
+        (point.z / size).floor() as i64,
+    )
+}
+
+/// Yields the 27 cells around `cell`, itself included, saturating instead of overflowing at the grid bounds.
+fn neighboring_cells((x, y, z): Cell) -> impl Iterator<Item = Cell> {
+    (-1..=1).flat_map(move |i| {
+        (-1..=1).flat_map(move |j| {
+            (-1..=1).map(move |k| {
+                (
+                    x.saturating_add(i),
+                    y.saturating_add(j),
+                    z.saturating_add(k),
+                )
+            })
+        })
+    })
+}
+
 /// This graph contains the edges between points as oriented segments.
+#[derive(Clone, PartialEq)]
 pub struct SegmentGraph {
     /// The adjacency list representation of the graph.
     pub(super) adjacencies: HashMap<Segment, HashSet<Segment>>,
+    /// The weights of the segments which have one.
+    pub(super) weights: HashMap<Segment, f32>,
 }
 
 impl SegmentGraph {
@@ -116,7 +535,7 @@ impl SegmentGraph {
                 subgraph
                     .points
                     .as_ref()
-                    .map_or(true, |values| values.contains(&point))
+                    .is_none_or(|values| values.contains(&point))
             })
             .for_each(|(&point, neighbors)| {
                 // using the `neighbors` of `point`, it links ingoing to outgoing segments
@@ -135,8 +554,95 @@ impl SegmentGraph {
                         }
                     });
             });
+        // keeps the weights of the segments belonging to the graph
+        let weights = subgraph
+            .graph
+            .weights
+            .iter()
+            .filter(|(segment, _)| graph.contains_key(*segment))
+            .map(|(&segment, &weight)| (segment, weight))
+            .collect();
         // instantiate the segment graph from its adjacency list
-        SegmentGraph { adjacencies: graph }
+        SegmentGraph {
+            adjacencies: graph,
+            weights,
+        }
+    }
+
+    /// Counts the nodes of the graph, namely the oriented segments having at least one successor.
+    ///
+    /// Both orientations of a segment are distinct nodes, thus this is about twice the number of input segments.
+    pub fn node_count(&self) -> usize {
+        self.adjacencies.len()
+    }
+
+    /// Counts the directed edges of the graph, namely each pair of consecutive oriented segments.
+    ///
+    /// Every undirected connection between two segments appears as two directed edges, one for each orientation,
+    /// thus the number of undirected edges is half of this count.
+    pub fn edge_count(&self) -> usize {
+        self.adjacencies.values().map(HashSet::len).sum()
+    }
+
+    /// Iterates over the segments that can follow `segment` when traversing the graph.
+    pub fn successors(&self, segment: &Segment) -> impl Iterator<Item = &Segment> {
+        self.adjacencies.get(segment).into_iter().flatten()
+    }
+
+    /// Yields the weight of `segment` which is `1` when it was not given.
+    pub fn weight(&self, segment: &Segment) -> f32 {
+        self.weights.get(segment).copied().unwrap_or(1f32)
+    }
+
+    /// Counts the segments that can follow `segment` when traversing the graph.
+    pub fn out_degree(&self, segment: &Segment) -> usize {
+        self.adjacencies.get(segment).map_or(0, HashSet::len)
+    }
+
+    /// Counts the segments that can precede `segment` when traversing the graph.
+    ///
+    /// Note that this scans the whole adjacency list thus it runs in linear time with the number of connections.
+    pub fn in_degree(&self, segment: &Segment) -> usize {
+        self.adjacencies
+            .values()
+            .filter(|successors| successors.contains(segment))
+            .count()
+    }
+}
+
+impl std::fmt::Debug for PointGraph {
+    /// Formats the adjacency list and the weights sorted by point, such that the output is stable.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PointGraph")
+            .field(
+                "adjacencies",
+                &self
+                    .adjacencies
+                    .iter()
+                    .map(|(point, neighbors)| (point, neighbors.iter().collect::<BTreeSet<_>>()))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+            .field("weights", &self.weights.iter().collect::<BTreeMap<_, _>>())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for SegmentGraph {
+    /// Formats the adjacency list and the weights sorted by segment, such that the output is stable.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentGraph")
+            .field(
+                "adjacencies",
+                &self
+                    .adjacencies
+                    .iter()
+                    .map(|(segment, successors)| {
+                        (segment, successors.iter().collect::<BTreeSet<_>>())
+                    })
+                    .collect::<BTreeMap<_, _>>(),
+            )
+            .field("weights", &self.weights.iter().collect::<BTreeMap<_, _>>())
+            .finish()
     }
 }
 
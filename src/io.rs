@@ -0,0 +1,330 @@
+use super::{
+    plane::Vector,
+    point::{Point, Segment},
+    polygon::Polygon,
+};
+
+use hashbrown::HashMap;
+use std::io::{BufRead, BufReader, Read, Result, Write};
+
+/// The error raised when reading segments from CSV, see [parse_segments_csv].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based number of the faulty line.
+    pub line: usize,
+    /// The description of the faulty column or of the failure.
+    pub description: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.description)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Constructs the buffer of unique vertices shared by `polygons` and, for each polygon, the 0-based indices of its
+/// vertices within the buffer, without repeating the closing one.
+fn indexed(polygons: &[Polygon]) -> (Vec<Point>, Vec<Vec<usize>>) {
+    let mut vertices = Vec::<Point>::new();
+    let mut positions = HashMap::<Point, usize>::new();
+    // maps each vertex of each polygon to its position in the buffer
+    let faces = polygons
+        .iter()
+        .map(|polygon| {
+            polygon
+                .edges()
+                .map(|(vertex, _)| {
+                    *positions.entry(vertex).or_insert_with(|| {
+                        vertices.push(vertex);
+                        vertices.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    (vertices, faces)
+}
+
+/// Decomposes `polygons` in triangles, see [Polygon::triangulate], each coming with its unit normal following the
+/// right-hand rule on its vertices.
+fn facets(polygons: &[Polygon]) -> Vec<(Vector, [Point; 3])> {
+    polygons
+        .iter()
+        .flat_map(Polygon::triangulate)
+        .map(|[a, b, c]| {
+            let normal = Vector::between(&(a, b)).cross(&Vector::between(&(a, c)));
+            (normal.normalize(), [a, b, c])
+        })
+        .collect()
+}
+
+/// Writes `polygons` to `writer` as a Wavefront OBJ mesh, that is the list of unique vertices as `v x y z` lines
+/// followed by one `f` line per polygon referencing its vertices through 1-based indices.
+pub fn export_obj(polygons: &[Polygon], writer: &mut impl Write) -> Result<()> {
+    let (vertices, faces) = indexed(polygons);
+
+    for vertex in vertices {
+        writeln!(writer, "v {:?} {:?} {:?}", vertex.x, vertex.y, vertex.z)?;
+    }
+
+    for face in faces {
+        writeln!(
+            writer,
+            "f {}",
+            face.iter()
+                .map(|index| (index + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes `polygons` to `writer` as a binary STL mesh, once decomposed in triangles through [Polygon::triangulate].
+///
+/// An empty 80-byte header and the number of triangles are followed, for each triangle, by its unit normal and its
+/// three vertices as little-endian `f32` values and by a zero attribute byte count.
+pub fn export_stl_binary(polygons: &[Polygon], writer: &mut impl Write) -> Result<()> {
+    let facets = facets(polygons);
+
+    writer.write_all(&[0u8; 80])?;
+    writer.write_all(&(facets.len() as u32).to_le_bytes())?;
+
+    for (normal, vertices) in facets {
+        for value in [normal.x, normal.y, normal.z].into_iter().chain(
+            vertices
+                .iter()
+                .flat_map(|vertex| [vertex.x, vertex.y, vertex.z]),
+        ) {
+            writer.write_all(&(value as f32).to_le_bytes())?;
+        }
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Like [export_stl_binary] but writes the textual variant of the STL format.
+pub fn export_stl_ascii(polygons: &[Polygon], writer: &mut impl Write) -> Result<()> {
+    writeln!(writer, "solid polygonum")?;
+
+    for (normal, vertices) in facets(polygons) {
+        writeln!(
+            writer,
+            "facet normal {:?} {:?} {:?}",
+            normal.x, normal.y, normal.z
+        )?;
+        writeln!(writer, "outer loop")?;
+        for vertex in vertices {
+            writeln!(
+                writer,
+                "vertex {:?} {:?} {:?}",
+                vertex.x, vertex.y, vertex.z
+            )?;
+        }
+        writeln!(writer, "endloop")?;
+        writeln!(writer, "endfacet")?;
+    }
+
+    writeln!(writer, "endsolid polygonum")
+}
+
+/// Writes `polygons` to `writer` as an ASCII PLY mesh, whose header declares the unique vertices and the faces,
+/// followed by the coordinates of the vertices and by the list of 0-based vertex indices of each polygon.
+///
+/// Unlike STL, the polygons are written as faces with an arbitrary number of vertices, whose count is thus declared
+/// as `uint` rather than the usual `uchar` limited to 255 vertices.
+pub fn export_ply(polygons: &[Polygon], writer: &mut impl Write) -> Result<()> {
+    let (vertices, faces) = indexed(polygons);
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "element vertex {}", vertices.len())?;
+    writeln!(writer, "property double x")?;
+    writeln!(writer, "property double y")?;
+    writeln!(writer, "property double z")?;
+    writeln!(writer, "element face {}", faces.len())?;
+    writeln!(writer, "property list uint uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    for vertex in vertices {
+        writeln!(writer, "{:?} {:?} {:?}", vertex.x, vertex.y, vertex.z)?;
+    }
+
+    for face in faces {
+        writeln!(
+            writer,
+            "{} {}",
+            face.len(),
+            face.iter()
+                .map(usize::to_string)
+                .collect::<Vec<String>>()
+                .join(" ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The plane on which the polygons are drawn by [export_svg].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SvgProjection {
+    /// Looks down from above, thus drawing the x and y coordinates.
+    TopDown,
+    /// Looks from the front, thus drawing the x and z coordinates.
+    FrontFace,
+}
+
+impl SvgProjection {
+    /// Projects `point` on the drawing plane, flipping the vertical axis which points down in SVG.
+    fn project(&self, point: &Point) -> (f64, f64) {
+        match self {
+            Self::TopDown => (point.x, -point.y),
+            Self::FrontFace => (point.x, -point.z),
+        }
+    }
+}
+
+/// Writes `polygons` to `writer` as an SVG image where each polygon becomes a `<polygon>` element, once projected on
+/// the plane described by `projection`.
+///
+/// The view box fits the bounding box of all polygons with a padding of 5% of its size.
+pub fn export_svg(
+    polygons: &[Polygon],
+    writer: &mut impl Write,
+    projection: SvgProjection,
+) -> Result<()> {
+    export_svg_with_fill(polygons, writer, projection, |_| None)
+}
+
+/// Like [export_svg] but each polygon is filled with the optional color given by `fill_color`, for instance
+/// `"#ff0000"` or `"red"`, while the polygons without color are not filled.
+pub fn export_svg_with_fill(
+    polygons: &[Polygon],
+    writer: &mut impl Write,
+    projection: SvgProjection,
+    fill_color: impl Fn(&Polygon) -> Option<String>,
+) -> Result<()> {
+    // the bounding box of all projected vertices
+    let (mut min, mut max) = (
+        (f64::INFINITY, f64::INFINITY),
+        (f64::NEG_INFINITY, f64::NEG_INFINITY),
+    );
+
+    for (x, y) in polygons
+        .iter()
+        .flat_map(Polygon::iter)
+        .map(|point| projection.project(&point))
+    {
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    // a unit view box is used when there is nothing to draw
+    if polygons.is_empty() {
+        (min, max) = ((0f64, 0f64), (1f64, 1f64));
+    }
+    // pads the view box, even when it is flat
+    let padding = 0.05 * (max.0 - min.0).max(max.1 - min.1).max(f64::EPSILON);
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:?} {:?} {:?} {:?}\">",
+        min.0 - padding,
+        min.1 - padding,
+        max.0 - min.0 + 2f64 * padding,
+        max.1 - min.1 + 2f64 * padding
+    )?;
+
+    for polygon in polygons {
+        writeln!(
+            writer,
+            "  <polygon points=\"{}\" fill=\"{}\" stroke=\"black\" vector-effect=\"non-scaling-stroke\"/>",
+            polygon
+                .edges()
+                .map(|(point, _)| {
+                    let (x, y) = projection.project(&point);
+                    format!("{:?},{:?}", x, y)
+                })
+                .collect::<Vec<String>>()
+                .join(" "),
+            fill_color(polygon).unwrap_or_else(|| "none".to_string())
+        )?;
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+/// The names of the columns of the CSV format of segments.
+const CSV_COLUMNS: [&str; 6] = ["x1", "y1", "z1", "x2", "y2", "z2"];
+
+/// Reads the segments from `reader` in CSV format, that is one `x1,y1,z1,x2,y2,z2` segment per line, while blank
+/// lines and comment lines starting with `#` are skipped.
+pub fn parse_segments_csv<R: Read>(reader: R) -> std::result::Result<Vec<Segment>, ParseError> {
+    let mut segments = Vec::new();
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let error = |description: String| ParseError {
+            line: index + 1,
+            description,
+        };
+        let line = line.map_err(|failure| error(failure.to_string()))?;
+        let line = line.trim();
+        // skips blank and comment lines
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // parses each coordinate while naming the faulty column
+        let fields = line.split(',').map(str::trim).collect::<Vec<&str>>();
+        if fields.len() != CSV_COLUMNS.len() {
+            return Err(error(format!(
+                "expected {} columns but found {}",
+                CSV_COLUMNS.len(),
+                fields.len()
+            )));
+        }
+        let mut coordinates = [0f64; 6];
+
+        for (column, (field, coordinate)) in fields.iter().zip(&mut coordinates).enumerate() {
+            *coordinate = field.parse().map_err(|_| {
+                error(format!(
+                    "column {} (`{}`) is not a number: `{}`",
+                    column + 1,
+                    CSV_COLUMNS[column],
+                    field
+                ))
+            })?;
+        }
+
+        let [x1, y1, z1, x2, y2, z2] = coordinates;
+        segments.push((
+            Point {
+                x: x1,
+                y: y1,
+                z: z1,
+            },
+            Point {
+                x: x2,
+                y: y2,
+                z: z2,
+            },
+        ));
+    }
+
+    Ok(segments)
+}
+
+/// Writes `segments` to `writer` in the CSV format read by [parse_segments_csv], without any header.
+pub fn write_segments_csv(segments: &[Segment], writer: &mut impl Write) -> Result<()> {
+    for (from, to) in segments {
+        writeln!(
+            writer,
+            "{:?},{:?},{:?},{:?},{:?},{:?}",
+            from.x, from.y, from.z, to.x, to.y, to.z
+        )?;
+    }
+
+    Ok(())
+}
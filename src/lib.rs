@@ -1,37 +1,285 @@
+pub mod bsp;
+pub mod config;
+#[cfg(feature = "geojson")]
+pub mod geojson;
 pub mod graph;
+pub mod io;
 pub mod pipeline;
 pub mod plane;
 pub mod point;
 pub mod polygon;
+pub mod strategy;
 pub mod traversal;
+#[cfg(feature = "wkt")]
+pub mod wkt;
 
+pub use config::PolygonalizeConfig;
+#[cfg(feature = "geojson")]
+pub use geojson::*;
 pub use graph::*;
 pub use pipeline::*;
+pub use plane::Vector;
 pub use point::*;
 pub use polygon::*;
+pub use strategy::{ElectionStrategy, GreedyElectionStrategy, MinimumAngleStrategy};
+#[cfg(feature = "wkt")]
+pub use wkt::WktError;
 
 /// Constructs a set of polygons from a set of [point::Segment]s.
 ///
 /// Filtering polygons is possible through `minimum_area_projected` and also
 /// parallel processing can be enabled through `parallelize`.
+///
+/// The polygons are sorted by their vertices, see [polygon::Polygon::cmp], such that the outcome is the same
+/// whether processed in parallel or not.
+///
+/// In debug builds, it panics when some segment is invalid, see [point::validate_segments].
 pub fn polygonalize(
     segments: &[point::Segment],
     parallelize: bool,
     minimum_area_projected: f64,
 ) -> Vec<polygon::Polygon> {
+    // at debug time rejects the segments which would silently spoil the construction
+    debug_assert!(
+        point::validate_segments(segments).is_ok(),
+        "invalid segments: {:?}",
+        point::validate_segments(segments)
+    );
+
+    polygonalize_with_config(
+        segments,
+        config::PolygonalizeConfig::default()
+            .parallelize(parallelize)
+            .minimum_area(minimum_area_projected),
+    )
+}
+
+/// Like [polygonalize] but all settings are given through `config`.
+pub fn polygonalize_with_config(
+    segments: &[point::Segment],
+    config: config::PolygonalizeConfig,
+) -> Vec<polygon::Polygon> {
+    polygonalize_detailed(segments, config).polygons
+}
+
+/// Like [polygonalize_with_config] but also yields some insights about the construction, such as how many polygons
+/// have been discarded when filtering them.
+pub fn polygonalize_detailed(
+    segments: &[point::Segment],
+    config: config::PolygonalizeConfig,
+) -> pipeline::PolygonalizeResult {
+    run(
+        pipeline::Pipeline::snapped(segments, config.snap_tolerance),
+        &config,
+    )
+}
+
+/// Like [polygonalize] but each segment comes with a positive weight expressing its reliability.
+///
+/// When traversing the graph, segments with higher weight are preferred as successors.
+///
+/// Fails on weights which are not finite and positive, see [graph::PointGraph::weighted].
+pub fn polygonalize_weighted(
+    segments: &[(point::Segment, f32)],
+    parallelize: bool,
+    minimum_area_projected: f64,
+) -> Result<Vec<polygon::Polygon>, graph::GraphError> {
+    Ok(run(
+        pipeline::Pipeline::weighted(segments)?,
+        &config::PolygonalizeConfig::default()
+            .parallelize(parallelize)
+            .minimum_area(minimum_area_projected),
+    )
+    .polygons)
+}
+
+/// Like [polygonalize_with_config] but the polygons are extracted by traversing the graph with the given
+/// `strategies`, applied one after the other, instead of the default ones, see [strategy::defaults].
+///
+/// Since the strategies are borrowed mutably, the connected components are always processed sequentially
+/// regardless of [PolygonalizeConfig::parallelize].
+pub fn polygonalize_with_strategies(
+    segments: &[point::Segment],
+    strategies: &mut [impl strategy::ElectionStrategy],
+    config: config::PolygonalizeConfig,
+) -> Vec<polygon::Polygon> {
+    let pipeline = pipeline::Pipeline::snapped(segments, config.snap_tolerance);
+    let offset = pipeline.offset().clone();
+
+    pipeline
+        .stream(|graph| {
+            polygon::filter_with(
+                traversal::traverse_with(&graph, strategies, config.max_depth, None),
+                config.minimum_area,
+                |polygon| config.accepts(polygon),
+            )
+        })
+        .map(|polygon| offset.restore_polygon(polygon))
+        .collect()
+}
+
+/// Like [polygonalize_with_config] but also yields the orphaned segments which have been removed as dead ends
+/// before constructing the polygons, for instance because they belong to incomplete polygons.
+///
+/// Segments collapsing into a single point by snapping are orphaned as well.
+pub fn polygonalize_with_orphans(
+    segments: &[point::Segment],
+    config: config::PolygonalizeConfig,
+) -> (Vec<polygon::Polygon>, Vec<point::Segment>) {
+    let pipeline = pipeline::Pipeline::snapped(segments, config.snap_tolerance);
+    let orphans = pipeline.orphans(segments);
+
+    (run(pipeline, &config).polygons, orphans)
+}
+
+/// Like [polygonalize_with_config] but lazily constructs the polygons, such that those of a connected component are
+/// yielded before processing the next one.
+///
+/// The connected components are always processed sequentially regardless of [PolygonalizeConfig::parallelize].
+pub fn polygonalize_streaming(
+    segments: &[point::Segment],
+    config: config::PolygonalizeConfig,
+) -> impl Iterator<Item = polygon::Polygon> {
+    let pipeline = pipeline::Pipeline::snapped(segments, config.snap_tolerance);
+    let offset = pipeline.offset().clone();
+
+    pipeline
+        .stream(move |graph| {
+            polygon::filter(
+                traversal::traverse_with(&graph, &mut strategy::defaults(), config.max_depth, None)
+                    .into_iter()
+                    .filter(|polygon| config.accepts(polygon))
+                    .collect(),
+                config.minimum_area,
+            )
+        })
+        .map(move |polygon| offset.restore_polygon(polygon))
+}
+
+/// Like [polygonalize] but reports the progress of the construction through `on_progress`, which is called at
+/// least once per [pipeline::Phase].
+///
+/// When processing sequentially the traversal is reported after each connected component, while the parallel
+/// processing reports it only once all components have been traversed, thus the progress is more approximate.
+pub fn polygonalize_with_progress(
+    segments: &[point::Segment],
+    parallelize: bool,
+    minimum_area_projected: f64,
+    mut on_progress: impl FnMut(pipeline::ProgressEvent),
+) -> Vec<polygon::Polygon> {
+    // constructs and prunes the graph of points
+    on_progress(pipeline::ProgressEvent {
+        phase: pipeline::Phase::Pruning,
+        progress: 0f32,
+    });
+
+    run_with_progress(
+        pipeline::Pipeline::from(segments),
+        &config::PolygonalizeConfig::default()
+            .parallelize(parallelize)
+            .minimum_area(minimum_area_projected),
+        on_progress,
+    )
+    .polygons
+}
+
+/// Like [polygonalize] but the construction can be interrupted by raising `cancel` from another thread, in which
+/// case [pipeline::Cancelled] is returned instead of the polygons found so far.
+///
+/// The flag is checked before processing each connected component and before traversing from each segment.
+pub fn polygonalize_cancellable(
+    segments: &[point::Segment],
+    parallelize: bool,
+    minimum_area_projected: f64,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> Result<Vec<polygon::Polygon>, pipeline::Cancelled> {
+    let pipeline = pipeline::Pipeline::from(segments);
+    let offset = pipeline.offset().clone();
+    // constructs the polygons from each graph while checking the flag
+    let transform = |graph| {
+        polygon::filter(
+            traversal::traverse(&graph, Some(&cancel)),
+            minimum_area_projected,
+        )
+        .map(|polygon| offset.restore_polygon(polygon))
+    };
+
     if parallelize {
+        pipeline.partition().apply_cancellable(transform, &cancel)
+    } else {
+        pipeline.apply_cancellable(transform, &cancel)
+    }
+    .map(|mut polygons| {
+        polygons.sort();
+        polygons
+    })
+}
+
+/// Summarizes the complexity of the graph constructed from `segments` once pruned of its dead ends, as it is
+/// processed by [polygonalize].
+pub fn stats(segments: &[point::Segment]) -> graph::GraphStats {
+    pipeline::Pipeline::from(segments).stats()
+}
+
+/// Constructs the polygons through `pipeline` according to `config`.
+fn run(
+    pipeline: pipeline::Pipeline,
+    config: &config::PolygonalizeConfig,
+) -> pipeline::PolygonalizeResult {
+    run_with_progress(pipeline, config, |_| {})
+}
+
+/// Like [run] but reports the progress of the construction through `on_progress`.
+fn run_with_progress(
+    pipeline: pipeline::Pipeline,
+    config: &config::PolygonalizeConfig,
+    mut on_progress: impl FnMut(pipeline::ProgressEvent),
+) -> pipeline::PolygonalizeResult {
+    let stats = pipeline.stats();
+    let offset = pipeline.offset().clone();
+    // constructs the polygons from a graph and filters them, while counting the found ones
+    let transform = |graph| {
+        let polygons =
+            traversal::traverse_with(&graph, &mut strategy::defaults(), config.max_depth, None);
+        let count = polygons.len();
+        let polygons = polygon::filter_with(polygons, config.minimum_area, |polygon| {
+            config.accepts(polygon)
+        })
+        .map(|polygon| offset.restore_polygon(polygon))
+        .collect::<Vec<_>>();
+
+        std::iter::once((count - polygons.len(), polygons))
+    };
+    let results = if config.parallelize {
         // parallel processing pipeline
-        pipeline::Pipeline::from(segments)
+        pipeline
             .partition()
-            .apply(|subgraph| {
-                // constructs the polygons from each subgraph and filters them
-                polygon::filter(traversal::traverse(&subgraph), minimum_area_projected)
-            })
+            .apply_with_progress(transform, &mut on_progress)
     } else {
         // sequential processing
-        pipeline::Pipeline::from(segments).apply(|graph| {
-            // constructs the polygons from the graph and filters them
-            polygon::filter(traversal::traverse(&graph), minimum_area_projected)
-        })
+        pipeline.apply_with_progress(transform, &mut on_progress)
+    };
+
+    // the polygons of the connected components are sorted altogether
+    on_progress(pipeline::ProgressEvent {
+        phase: pipeline::Phase::Filtering,
+        progress: 0.9,
+    });
+    let discarded_count = results.iter().map(|(discarded, _)| discarded).sum();
+    let mut polygons = results
+        .into_iter()
+        .flat_map(|(_, polygons)| polygons)
+        .collect::<Vec<_>>();
+    polygons.sort();
+    on_progress(pipeline::ProgressEvent {
+        phase: pipeline::Phase::Filtering,
+        progress: 1f32,
+    });
+
+    pipeline::PolygonalizeResult {
+        discarded_count,
+        polygons,
+        pruned_node_count: stats.pruned_node_count,
+        component_count: stats.component_count,
     }
 }
@@ -1,34 +1,301 @@
 use super::{
-    graph::{PointGraph, SegmentGraph},
+    graph::{GraphError, GraphStats, PointGraph, SegmentGraph},
     point::{Point, Segment},
+    polygon::Polygon,
 };
 
-use hashbrown::HashSet;
+use hashbrown::HashMap;
 use rayon::prelude::*;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The phases of the construction of polygons from segments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The graph of points is constructed and its dead ends are removed.
+    Pruning,
+    /// The graph of points is split into its connected components.
+    Partitioning,
+    /// Each connected component is traversed to construct the polygons.
+    Traversing,
+    /// The constructed polygons are filtered.
+    Filtering,
+}
+
+/// Notification about the progress of the construction of polygons from segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProgressEvent {
+    /// The phase being currently run.
+    pub phase: Phase,
+    /// The overall progress within `[0, 1]` which never decreases along the construction.
+    pub progress: f32,
+}
+
+/// The error returned when the processing is interrupted by raising its cancellation flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the processing has been cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// The polygons constructed from segments together with some insights about the construction.
+#[derive(Debug)]
+pub struct PolygonalizeResult {
+    /// The constructed polygons.
+    pub polygons: Vec<Polygon>,
+    /// The number of polygons found by the traversal but discarded when filtering them.
+    pub discarded_count: usize,
+    /// The number of points removed from the graph as dead ends.
+    pub pruned_node_count: usize,
+    /// The number of connected components of the pruned graph.
+    pub component_count: usize,
+}
+
+/// The translation bringing the segments close to the origin before processing them, which avoids catastrophic
+/// cancellation with large coordinates such as UTM eastings and northings.
+///
+/// The translation is the centroid of the endpoints of the segments rounded to a multiple of [Offset::GRANULARITY],
+/// hence it vanishes for segments already close to the origin and translating coordinates of similar magnitude is
+/// exact. Coordinates of very different magnitude might still be rounded, in which case the translation is skipped
+/// altogether as soon as two distinct endpoints would be merged. Otherwise, the graphs handed to the transformations of a [Pipeline] are translated and their polygons must
+/// be restored through [Offset::restore_polygon] to recover the original coordinates.
+#[derive(Clone, Debug)]
+pub struct Offset {
+    /// The rounded centroid of the endpoints of the segments, which is subtracted from every point.
+    centroid: Point,
+    /// The original location of each translated endpoint.
+    origins: Arc<HashMap<Point, Point>>,
+}
+
+impl Offset {
+    /// The granularity of the translation, as a power of two.
+    pub const GRANULARITY: f64 = 1024f64;
+
+    /// Constructs the translation moving the centroid of the endpoints of `segments` close to the origin.
+    fn from(segments: impl Iterator<Item = Segment> + Clone) -> Self {
+        // the centroid of all endpoints
+        let count = 2 * segments.clone().count();
+        let (x, y, z) = segments
+            .clone()
+            .flat_map(|(from, to)| [from, to])
+            .fold((0f64, 0f64, 0f64), |(x, y, z), point| {
+                (x + point.x, y + point.y, z + point.z)
+            });
+        let mut offset = Self {
+            centroid: if count == 0 {
+                Point::from([0f64; 3])
+            } else {
+                Point::from([x, y, z].map(|value| {
+                    (value / count as f64 / Self::GRANULARITY).round() * Self::GRANULARITY
+                }))
+            },
+            origins: Arc::default(),
+        };
+        // remembers the original points such that they are restored exactly, unless nothing moves
+        if offset.is_identity() {
+            return offset;
+        }
+        let mut origins = HashMap::new();
+        for point in segments.flat_map(|(from, to)| [from, to]) {
+            // distinct points merged by rounding must not be translated at all
+            if *origins.entry(offset.translate(point)).or_insert(point) != point {
+                return Self {
+                    centroid: Point::from([0f64; 3]),
+                    origins: Arc::default(),
+                };
+            }
+        }
+        offset.origins = Arc::new(origins);
+
+        offset
+    }
+
+    /// Yields the rounded centroid of the segments which has been moved to the origin.
+    pub fn centroid(&self) -> Point {
+        self.centroid
+    }
+
+    /// Checks whether the translation leaves the points unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.centroid == Point::from([0f64; 3])
+    }
+
+    /// Moves `point` by the translation.
+    fn translate(&self, point: Point) -> Point {
+        Point::from([
+            point.x - self.centroid.x,
+            point.y - self.centroid.y,
+            point.z - self.centroid.z,
+        ])
+    }
+
+    /// Recovers the original location of the translated `point`, which is exact for the endpoints of the segments.
+    pub fn restore(&self, point: Point) -> Point {
+        self.origins.get(&point).copied().unwrap_or_else(|| {
+            Point::from([
+                point.x + self.centroid.x,
+                point.y + self.centroid.y,
+                point.z + self.centroid.z,
+            ])
+        })
+    }
+
+    /// Recovers the original location of the vertices of the translated `polygon`.
+    pub fn restore_polygon(&self, polygon: Polygon) -> Polygon {
+        if self.is_identity() {
+            return polygon;
+        }
+        // the closing vertex repeats the opening one
+        let mut vertices = polygon.into_iter().collect::<Vec<Point>>();
+        vertices.pop();
+
+        Polygon::from(
+            vertices
+                .into_iter()
+                .map(|vertex| self.restore(vertex))
+                .collect(),
+        )
+    }
+}
 
 /// A pipeline processes a list of segments and delivers a set of polygons.
 pub struct Pipeline {
     /// The adjacency list that represents the graph of points.
     graph: PointGraph,
+    /// The number of points removed by pruning the graph.
+    pruned_node_count: usize,
+    /// The points moved by snapping, mapped to their new location.
+    snapping: HashMap<Point, Point>,
+    /// The translation applied to the segments.
+    offset: Offset,
 }
 
 impl Pipeline {
     /// Instantiate the pipeline from a set of segments.
+    ///
+    /// The segments are translated such that the centroid of their endpoints lies close to the origin, see [Offset].
     pub fn from(segments: &[Segment]) -> Self {
+        Self::snapped(segments, 0f64)
+    }
+
+    /// Like [Pipeline::from] but first merges the points lying within `tolerance` of each other, see
+    /// [PointGraph::snap_close_vertices], unless `tolerance` is zero.
+    pub fn snapped(segments: &[Segment], tolerance: f64) -> Self {
+        let offset = Offset::from(segments.iter().copied());
+        let graph = PointGraph::from(
+            &segments
+                .iter()
+                .map(|&(from, to)| (offset.translate(from), offset.translate(to)))
+                .collect::<Vec<Segment>>(),
+        );
+        // snaps the graph before pruning so that close dead ends can be joined together
+        if tolerance > 0f64 {
+            let snapping = graph.snapping(tolerance);
+            Self {
+                snapping: snapping
+                    .iter()
+                    .filter(|(from, to)| from != to)
+                    .map(|(&from, &to)| (from, to))
+                    .collect(),
+                offset,
+                ..Self::pruned(graph.snapped(&snapping))
+            }
+        } else {
+            Self {
+                offset,
+                ..Self::pruned(graph)
+            }
+        }
+    }
+
+    /// Instantiate the pipeline from a set of segments with their positive weights.
+    ///
+    /// Fails on weights which are not finite and positive, see [PointGraph::weighted].
+    pub fn weighted(segments: &[(Segment, f32)]) -> Result<Self, GraphError> {
+        // reports the invalid segments before translating them
+        PointGraph::validate_weights(segments)?;
+        let offset = Offset::from(segments.iter().map(|&(segment, _)| segment));
+        let graph = PointGraph::weighted(
+            &segments
+                .iter()
+                .map(|&((from, to), weight)| {
+                    ((offset.translate(from), offset.translate(to)), weight)
+                })
+                .collect::<Vec<(Segment, f32)>>(),
+        )?;
+
+        Ok(Self {
+            offset,
+            ..Self::pruned(graph)
+        })
+    }
+
+    /// Instantiate the pipeline from a graph which is pruned by removing its dead ends.
+    fn pruned(graph: PointGraph) -> Self {
+        // the number of points before pruning
+        let count = graph.adjacencies.len();
+        let graph = graph.prune();
+
         Self {
-            // prune the graph by removing dead ends
-            graph: PointGraph::from(segments).prune(),
+            pruned_node_count: count - graph.adjacencies.len(),
+            graph,
+            snapping: HashMap::new(),
+            offset: Offset::from(std::iter::empty()),
         }
     }
 
+    /// Detects which of the original `segments` have been removed from the graph by pruning, once their endpoints
+    /// are snapped as the graph was.
+    pub fn orphans(&self, segments: &[Segment]) -> Vec<Segment> {
+        // the location of a point after translating and snapping it
+        let snap = |point: &Point| {
+            let point = self.offset.translate(*point);
+            self.snapping.get(&point).copied().unwrap_or(point)
+        };
+
+        segments
+            .iter()
+            .filter(|(from, to)| {
+                !self
+                    .graph
+                    .adjacencies
+                    .get(&snap(from))
+                    .is_some_and(|neighbors| neighbors.contains(&snap(to)))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Summarizes the complexity of the pruned graph.
+    pub fn stats(&self) -> GraphStats {
+        self.graph.stats(self.pruned_node_count)
+    }
+
+    /// Yields the translation applied to the segments, which the polygons must be restored from.
+    pub fn offset(&self) -> &Offset {
+        &self.offset
+    }
+
     /// Takes ownership of the pipeline to construct a pipeline doing parallel processesing on the graph's
     /// connected components.
+    ///
+    /// The graphs remain translated by [Pipeline::offset], which should be kept beforehand to restore the outputs.
     pub fn partition(self) -> PartitionPipeline {
         PartitionPipeline { graph: self.graph }
     }
 
     /// Applies a transformation function to the constructed [SegmentGraph] and collects the outputs as a vector.
     ///
+    /// The graph is translated by [Pipeline::offset], thus the points it yields must be restored through
+    /// [Offset::restore] to recover the original coordinates.
+    ///
     /// Note that this performs sequential processing and might be slow for large graphs where [PartitionPipeline]
     /// is suggested.
     pub fn apply<F, I, R>(&self, transform: F) -> Vec<R>
@@ -40,6 +307,89 @@ impl Pipeline {
         // constructs the full graph of segments
         transform(SegmentGraph::from(&self.graph.fullgraph())).collect::<Vec<R>>()
     }
+
+    /// Takes ownership of the pipeline to lazily apply `transform` on each connected component, one after the other,
+    /// such that the outputs of a component are yielded before processing the next one.
+    ///
+    /// As with [Pipeline::apply], the graphs are translated by [Pipeline::offset].
+    pub fn stream<F, I, R>(self, mut transform: F) -> impl Iterator<Item = R>
+    where
+        I: Iterator<Item = R>,
+        F: FnMut(SegmentGraph) -> I,
+    {
+        // the connected components are detected upfront but processed only when needed
+        let components = self.graph.connected_components().collect::<Vec<_>>();
+        let graph = self.graph;
+
+        components
+            .into_iter()
+            .flat_map(move |points| transform(SegmentGraph::from(&graph.subgraph(points))))
+    }
+
+    /// Like [Pipeline::apply] but transforms the connected components one after the other, reporting through
+    /// `on_progress` when partitioning the graph and then after each transformed component.
+    ///
+    /// The reported progress goes from 0.1 up to 0.9, leaving room for the phases before and after.
+    pub fn apply_with_progress<F, I, R>(
+        &self,
+        transform: F,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> Vec<R>
+    where
+        I: Iterator<Item = R>,
+        F: Fn(SegmentGraph) -> I + Send + Sync,
+        R: Send + Sync,
+    {
+        on_progress(ProgressEvent {
+            phase: Phase::Partitioning,
+            progress: 0.1,
+        });
+        let components = self.graph.connected_components().collect::<Vec<_>>();
+        let count = components.len();
+        on_progress(ProgressEvent {
+            phase: Phase::Traversing,
+            progress: 0.2,
+        });
+
+        components
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, points)| {
+                let results =
+                    transform(SegmentGraph::from(&self.graph.subgraph(points))).collect::<Vec<R>>();
+                on_progress(ProgressEvent {
+                    phase: Phase::Traversing,
+                    progress: 0.2 + 0.7 * (index + 1) as f32 / count as f32,
+                });
+                results
+            })
+            .collect::<Vec<R>>()
+    }
+
+    /// Like [Pipeline::apply] but yields [Cancelled] when `cancel` is raised before or while transforming the graph.
+    ///
+    /// Note that `transform` should check `cancel` on its own to stop early.
+    pub fn apply_cancellable<F, I, R>(
+        &self,
+        transform: F,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<R>, Cancelled>
+    where
+        I: Iterator<Item = R>,
+        F: Fn(SegmentGraph) -> I + Send + Sync,
+        R: Send + Sync,
+    {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(Cancelled);
+        }
+        let results = self.apply(transform);
+        // the results might be partial when cancelled in the meantime
+        if cancel.load(Ordering::Relaxed) {
+            Err(Cancelled)
+        } else {
+            Ok(results)
+        }
+    }
 }
 
 /// This pipeline is constructed from [Pipeline] to parallelize processing across disconnected [SegmentGraph]s.
@@ -58,25 +408,9 @@ impl PartitionPipeline {
         F: Fn(SegmentGraph) -> I + Send + Sync,
         R: Send + Sync,
     {
-        // explored vertices when identifying connected components
-        let mut explored = HashSet::<Point>::new();
         // first instantiate each graph as an independent connected component and performs parallel processing
         self.graph
-            .adjacencies
-            .keys()
-            .filter_map(|point| {
-                // constructs each connected component from the graph of points first
-                if !explored.contains(point) {
-                    // if the point has not been visited yet it will detect its associated connected component
-                    let mut points = HashSet::<Point>::new();
-                    // recursive exploration as depth first traversal
-                    self.explore(point, &mut explored, &mut points);
-                    // returns the list of points as a connected component
-                    Some(points)
-                } else {
-                    None
-                }
-            })
+            .connected_components()
             .par_bridge()
             .flat_map_iter(|points| {
                 // this will run in parallel for each connected component given by an independent graph of points
@@ -87,22 +421,64 @@ impl PartitionPipeline {
             .collect::<Vec<R>>()
     }
 
-    /// Performs a depth first search from node `point` to detect all points in connected component `partition`.
-    fn explore(
+    /// Like [PartitionPipeline::apply] but reports through `on_progress` when partitioning the graph, when starting
+    /// to transform the connected components and once all of them have been transformed.
+    ///
+    /// Unlike [Pipeline::apply_with_progress] the progress is not reported after each component since they are
+    /// transformed in parallel, thus it is more approximate.
+    pub fn apply_with_progress<F, I, R>(
         &self,
-        point: &Point,
-        explored: &mut HashSet<Point>,
-        partition: &mut HashSet<Point>,
-    ) {
-        // visit only if not visited already
-        if !explored.contains(point) {
-            // point is added to the connected component
-            explored.insert(*point);
-            partition.insert(*point);
-            // recursive traversal is applied to each of its neighboring points
-            self.graph.adjacencies[point].iter().for_each(|neighbor| {
-                self.explore(neighbor, explored, partition);
-            });
+        transform: F,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> Vec<R>
+    where
+        I: Iterator<Item = R>,
+        F: Fn(SegmentGraph) -> I + Send + Sync,
+        R: Send + Sync,
+    {
+        on_progress(ProgressEvent {
+            phase: Phase::Partitioning,
+            progress: 0.1,
+        });
+        on_progress(ProgressEvent {
+            phase: Phase::Traversing,
+            progress: 0.2,
+        });
+        let results = self.apply(transform);
+        on_progress(ProgressEvent {
+            phase: Phase::Traversing,
+            progress: 0.9,
+        });
+
+        results
+    }
+
+    /// Like [PartitionPipeline::apply] but yields [Cancelled] when `cancel` is raised before or while transforming
+    /// the graphs, in which case the remaining connected components are skipped.
+    ///
+    /// Note that `transform` should check `cancel` on its own to stop early.
+    pub fn apply_cancellable<F, I, R>(
+        &self,
+        transform: F,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<R>, Cancelled>
+    where
+        I: Iterator<Item = R>,
+        F: Fn(SegmentGraph) -> I + Send + Sync,
+        R: Send + Sync,
+    {
+        // skips every connected component left once cancelled
+        let results = self.apply(|graph| {
+            (!cancel.load(Ordering::Relaxed))
+                .then(|| transform(graph))
+                .into_iter()
+                .flatten()
+        });
+        // the results might be partial when cancelled in the meantime
+        if cancel.load(Ordering::Relaxed) {
+            Err(Cancelled)
+        } else {
+            Ok(results)
         }
     }
 }
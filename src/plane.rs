@@ -1,11 +1,14 @@
-use super::point::{Point, Segment};
+use super::{
+    point::{Point, Segment},
+    polygon::Polygon,
+};
 
 /// A three dimensional vector.
 #[derive(Clone, Copy, Debug)]
-pub(super) struct Vector {
-    pub(super) x: f64,
-    pub(super) y: f64,
-    pub(super) z: f64,
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
 }
 
 impl Vector {
@@ -42,12 +45,12 @@ impl Vector {
     }
 
     /// Computes the euclidean norm of the vector.
-    pub(super) fn norm(&self) -> f64 {
+    pub fn norm(&self) -> f64 {
         (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
     }
 
     /// Normalizes the vector.
-    pub(super) fn normalize(&self) -> Vector {
+    pub fn normalize(&self) -> Vector {
         // first computes its norm
         let norm = self.norm();
         // if the vector is zero it cannot be normalized at all
@@ -62,8 +65,8 @@ impl Vector {
         }
     }
 
-    // Computes the asymmetric cross product with `other`.
-    pub(super) fn cross(&self, other: &Self) -> Self {
+    /// Computes the asymmetric cross product with `other`.
+    pub fn cross(&self, other: &Self) -> Self {
         Self {
             x: self.y * other.z - self.z * other.y,
             y: self.z * other.x - self.x * other.z,
@@ -71,8 +74,8 @@ impl Vector {
         }
     }
 
-    // Computes the symmetric scalar product with `other`.
-    pub(super) fn dot(&self, other: &Self) -> f64 {
+    /// Computes the symmetric scalar product with `other`.
+    pub fn dot(&self, other: &Self) -> f64 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
@@ -103,6 +106,74 @@ impl Vector {
         }
     }
 
+    /// Linearly interpolates towards `other` where `t = 0` yields `self` and `t = 1` yields `other`.
+    pub(super) fn lerp(&self, other: &Self, t: f64) -> Self {
+        self.scale(1f64 - t).add(&other.scale(t))
+    }
+
+    /// Spherically interpolates between two unit vectors along the great circle connecting them.
+    ///
+    /// Unlike [Self::lerp], the interpolated vector keeps unit length, which makes it suitable for blending normals.
+    /// When the two vectors are almost aligned the procedure falls back to [Self::lerp] to avoid dividing by zero,
+    /// while almost opposite vectors, joined by infinitely many great circles, are rotated around an arbitrary
+    /// perpendicular axis.
+    #[allow(dead_code)]
+    pub(super) fn slerp(&self, other: &Self, t: f64) -> Self {
+        // angle between the two unit vectors, clamping the cosine against rounding errors
+        let theta = self.dot(other).clamp(-1f64, 1f64).acos();
+        // nearly identical vectors are interpolated linearly
+        if theta < f64::EPSILON.sqrt() {
+            return self.lerp(other, t);
+        }
+        // nearly opposite vectors follow the great circle through any perpendicular vector
+        if std::f64::consts::PI - theta < f64::EPSILON.sqrt() {
+            let (perpendicular, _) = orthogonal_basis(*self);
+            let angle = t * std::f64::consts::PI;
+            return self
+                .scale(angle.cos())
+                .add(&perpendicular.scale(angle.sin()));
+        }
+        // weights of the two vectors along the great circle
+        let sin = theta.sin();
+        self.scale(((1f64 - t) * theta).sin() / sin)
+            .add(&other.scale((t * theta).sin() / sin))
+    }
+
+    /// Computes the unsigned angle in radians with `other`, which lies within `[0, π]`.
+    ///
+    /// The angle is not defined and [f64::NAN] is returned when either vector is the zero vector.
+    pub fn angle_with(&self, other: &Vector) -> f64 {
+        let norms = self.norm() * other.norm();
+        if norms <= f64::EPSILON {
+            return f64::NAN;
+        }
+        // clamps the cosine against rounding errors
+        (self.dot(other) / norms).clamp(-1f64, 1f64).acos()
+    }
+
+    /// Checks whether the vector is parallel to `other`, in the same or opposite direction, within `tolerance_rad`
+    /// radians.
+    pub fn is_parallel(&self, other: &Vector, tolerance_rad: f64) -> bool {
+        let angle = self.angle_with(other);
+        angle <= tolerance_rad || angle >= std::f64::consts::PI - tolerance_rad
+    }
+
+    /// Checks whether the vector is perpendicular to `other` within `tolerance_rad` radians.
+    pub fn is_perpendicular(&self, other: &Vector, tolerance_rad: f64) -> bool {
+        (self.angle_with(other) - std::f64::consts::FRAC_PI_2).abs() <= tolerance_rad
+    }
+
+    /// Rotates the vector by `angle_rad` radians counterclockwise around `axis`, which does not need to be a unit
+    /// vector, through the Rodrigues' rotation formula.
+    pub fn rotate(&self, axis: Vector, angle_rad: f64) -> Vector {
+        let axis = axis.normalize();
+        let (sin, cos) = angle_rad.sin_cos();
+
+        self.scale(cos)
+            .add(&axis.cross(self).scale(sin))
+            .add(&axis.scale(axis.dot(self) * (1f64 - cos)))
+    }
+
     // Computes the clockwise angle with `other` projected on the xy plane.
     pub(super) fn theta(&self, other: &Self) -> f64 {
         std::f64::consts::PI
@@ -110,6 +181,47 @@ impl Vector {
     }
 }
 
+impl std::ops::Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Self) -> Self::Output {
+        Vector::add(&self, &other)
+    }
+}
+
+impl std::ops::Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.subtract(&other)
+    }
+}
+
+impl std::ops::Mul<f64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: f64) -> Self::Output {
+        self.scale(factor)
+    }
+}
+
+impl std::ops::Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        self.scale(-1f64)
+    }
+}
+
+impl PartialEq for Vector {
+    /// Vectors are equal when their coordinates differ at most by [f64::EPSILON].
+    fn eq(&self, other: &Self) -> bool {
+        (self.x - other.x).abs() <= f64::EPSILON
+            && (self.y - other.y).abs() <= f64::EPSILON
+            && (self.z - other.z).abs() <= f64::EPSILON
+    }
+}
+
 /// Computes the clockwise angle projected on the xy plane between two consecutive segments.
 #[inline]
 pub(super) fn theta(a: &Segment, b: &Segment) -> f64 {
@@ -119,29 +231,92 @@ pub(super) fn theta(a: &Segment, b: &Segment) -> f64 {
 /// Computes the coplanarity between four points as the volume of the described tetrahedron.
 #[inline]
 pub(super) fn coplanarity(a: Point, b: Point, c: Point, d: Point) -> f64 {
-    Vector::between(&(a, b))
-        .cross(&Vector::between(&(a, c)))
-        .dot(&Vector::between(&(a, d)))
-        .abs()
-        / 6f64
+    robust_orientation(a, b, c, d).abs() / 6f64
+}
+
+/// Computes the orientation of `d` with respect to the plane through `a`, `b` and `c`, namely the determinant
+/// `((b - a) × (c - a)) · (d - a)` which is six times the signed volume of the tetrahedron.
+///
+/// It is positive when `d` lies on the side pointed by the normal of the triangle `a`, `b`, `c` taken
+/// counterclockwise, negative on the other side and zero when the points are coplanar.
+///
+/// Following the adaptive approach of Shewchuk's predicates, the plain floating-point determinant is returned when
+/// its magnitude exceeds an error bound derived from the magnitudes of its terms, which is the common case. Otherwise
+/// the determinant is summed again from its error-free products through compensated summation, such that nearly
+/// coplanar configurations, typical of large coordinates, still get the right sign.
+pub fn robust_orientation(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let (u, v, w) = (
+        Vector::between(&(a, b)),
+        Vector::between(&(a, c)),
+        Vector::between(&(a, d)),
+    );
+    // fast floating-point determinant
+    let determinant = u.cross(&v).dot(&w);
+    // the error bound is proportional to the permanent, that is the sum of the magnitudes of the terms
+    let permanent = ((u.y * v.z).abs() + (u.z * v.y).abs()) * w.x.abs()
+        + ((u.z * v.x).abs() + (u.x * v.z).abs()) * w.y.abs()
+        + ((u.x * v.y).abs() + (u.y * v.x).abs()) * w.z.abs();
+    let epsilon = f64::EPSILON / 2f64;
+    let bound = (7f64 + 56f64 * epsilon) * epsilon * permanent;
+
+    if determinant.abs() > bound {
+        return determinant;
+    }
+    // splits the product into its rounded value and its exact rounding error
+    let product = |x: f64, y: f64| {
+        let value = x * y;
+        (value, x.mul_add(y, -value))
+    };
+    // the six signed terms of the determinant expanded along `w`
+    let terms = [
+        (u.y, v.z, w.x),
+        (-u.z, v.y, w.x),
+        (u.z, v.x, w.y),
+        (-u.x, v.z, w.y),
+        (u.x, v.y, w.z),
+        (-u.y, v.x, w.z),
+    ];
+    // sums the terms and their rounding errors with Neumaier's compensation
+    let (mut sum, mut compensation) = (0f64, 0f64);
+
+    for (x, y, z) in terms {
+        let (high, low) = product(x, y);
+        let (value, error) = product(high, z);
+
+        for part in [value, error, low * z] {
+            let next = sum + part;
+            compensation += if sum.abs() >= part.abs() {
+                (sum - next) + part
+            } else {
+                (part - next) + sum
+            };
+            sum = next;
+        }
+    }
+
+    sum + compensation
 }
 
 /// Computes the normal vector of the plane described by a polygon enclosed by a set of `vertices`.
+///
+/// It follows Newell's method, summing `(v_i - v_j) × (v_i + v_j)` over the consecutive vertices, which is twice the
+/// sum of their cross products without requiring to center the vertices. The closing side is considered whether the
+/// last vertex repeats the first one or not, since a repeated vertex contributes nothing.
 #[inline]
 pub(super) fn normal(vertices: &[Point]) -> Vector {
-    // computes the center of the polygon to reduce big coordinates values in the computation and stabilize it
-    let offset = center(vertices);
-    // ensures that the last vertices corresponds to the first
-    debug_assert_eq!(vertices.first(), vertices.last());
     // computes the normal describing the polygon's plane
-    (0..(vertices.len() - 1))
+    (0..vertices.len())
         .map(|index| {
-            Vector::from(&vertices[index])
-                .subtract(&offset)
-                .cross(&Vector::from(&vertices[index + 1]).subtract(&offset))
+            let (current, next) = (
+                Vector::from(&vertices[index]),
+                Vector::from(&vertices[(index + 1) % vertices.len()]),
+            );
+            current.subtract(&next).cross(&current.add(&next))
         })
-        .reduce(|accumulator, element| accumulator.add(&element))
-        .unwrap()
+        .fold(Vector::zero(), |accumulator, element| {
+            accumulator.add(&element)
+        })
+        .scale(0.5)
 }
 
 /// Computes the unweighted center point of a polygon.
@@ -157,3 +332,408 @@ pub(super) fn center(vertices: &[Point]) -> Vector {
         .map(|total| total.scale(1f64 / (vertices.len() - 1) as f64))
         .unwrap()
 }
+
+/// Computes the signed distance of `point` from the plane passing through `origin` with unit `normal`.
+#[inline]
+pub(super) fn distance_point_to_plane(point: &Point, origin: &Point, normal: &Vector) -> f64 {
+    Vector::between(&(*origin, *point)).dot(normal)
+}
+
+/// Projects `point` orthogonally onto the plane passing through `plane_point` with direction `plane_normal`, which
+/// does not need to be a unit vector.
+///
+/// A zero `plane_normal` describes no plane and leaves `point` untouched.
+pub fn project_point_onto_plane(point: Point, plane_point: Point, plane_normal: Vector) -> Point {
+    let normal = plane_normal.normalize();
+    let offset = normal.scale(distance_point_to_plane(&point, &plane_point, &normal));
+
+    Point {
+        x: point.x - offset.x,
+        y: point.y - offset.y,
+        z: point.z - offset.z,
+    }
+}
+
+/// Computes the point of `segment` which is closest to `point` by projecting the latter on the line through the
+/// former and clamping the projection within the endpoints.
+pub fn segment_closest_point(segment: Segment, point: Point) -> Point {
+    let direction = Vector::between(&segment);
+    let length = direction.dot(&direction);
+    // degenerate segments collapse on their first endpoint
+    if length <= f64::EPSILON {
+        return segment.0;
+    }
+    // the parameter of the projection along the segment
+    let t = Vector::between(&(segment.0, point)).dot(&direction) / length;
+    segment.0.lerp_3d(&segment.1, t.clamp(0f64, 1f64))
+}
+
+/// Computes the distance between `point` and the closest point of `segment`.
+pub fn segment_distance_to_point(segment: Segment, point: Point) -> f64 {
+    segment_closest_point(segment, point).distance(&point)
+}
+
+/// Computes where the segments `a` and `b` meet, namely the midpoint of their closest points when these lie within
+/// `tolerance` of each other.
+///
+/// Parallel segments, including overlapping collinear ones, have no single meeting point and yield nothing.
+#[allow(dead_code)]
+pub(super) fn intersect_segments(a: Segment, b: Segment, tolerance: f64) -> Option<Point> {
+    let (u, v, w) = (
+        Vector::between(&a),
+        Vector::between(&b),
+        Vector::between(&(b.0, a.0)),
+    );
+    let (uu, uv, vv, uw, vw) = (u.dot(&u), u.dot(&v), v.dot(&v), u.dot(&w), v.dot(&w));
+    // the lines are parallel, or a segment degenerates into a point, when the determinant vanishes
+    let determinant = uu * vv - uv * uv;
+    if determinant <= f64::EPSILON * uu * vv || uu <= f64::EPSILON || vv <= f64::EPSILON {
+        return None;
+    }
+    // the parameters of the closest points along each line
+    let (s, t) = (
+        (uv * vw - vv * uw) / determinant,
+        (uu * vw - uv * uw) / determinant,
+    );
+    // the closest points must belong to the segments
+    if !(0f64..=1f64).contains(&s) || !(0f64..=1f64).contains(&t) {
+        return None;
+    }
+    let (p, q) = (a.0.lerp_3d(&a.1, s), b.0.lerp_3d(&b.1, t));
+    (p.distance(&q) <= tolerance).then(|| super::point::segment_midpoint((p, q)))
+}
+
+/// Fits the plane minimizing the total squared distance from `points` through principal component analysis, and
+/// yields it as the centroid of the points together with the unit normal, oriented with non-negative z.
+///
+/// No plane is fitted when `points` are less than three or when they are all collinear.
+pub fn best_fit_plane(points: &[Point]) -> Option<(Point, Vector)> {
+    if points.len() < 3 {
+        return None;
+    }
+    // the centroid of the points lies on the best fitting plane
+    let count = points.len() as f64;
+    let centroid = points
+        .iter()
+        .map(Vector::from)
+        .fold(Vector::zero(), |total, point| total.add(&point))
+        .scale(1f64 / count);
+    // the covariance matrix of the points around their centroid
+    let mut covariance = [[0f64; 3]; 3];
+    for point in points {
+        let offset = Vector::from(point).subtract(&centroid);
+        let offset = [offset.x, offset.y, offset.z];
+
+        for i in 0..3 {
+            for j in 0..3 {
+                covariance[i][j] += offset[i] * offset[j] / count;
+            }
+        }
+    }
+    // the normal is the direction of least variance, while collinear points vary along a single direction
+    let (values, vectors) = eigen(covariance);
+    let mut order = [0usize, 1, 2];
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    if values[order[1]] <= 1e-12 * values[order[2]] || values[order[2]] <= 0f64 {
+        return None;
+    }
+    let normal = Vector {
+        x: vectors[0][order[0]],
+        y: vectors[1][order[0]],
+        z: vectors[2][order[0]],
+    }
+    .normalize();
+    let normal = if normal.z < 0f64 {
+        normal.scale(-1f64)
+    } else {
+        normal
+    };
+
+    Some((
+        Point {
+            x: centroid.x,
+            y: centroid.y,
+            z: centroid.z,
+        },
+        normal,
+    ))
+}
+
+/// Diagonalizes the symmetric `matrix` through the cyclic Jacobi method, yielding its eigenvalues together with the
+/// matrix whose columns are the corresponding eigenvectors.
+fn eigen(mut matrix: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut vectors = [[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]];
+    // a handful of sweeps is more than enough for such a small matrix
+    for _ in 0..64 {
+        let off = matrix[0][1].abs() + matrix[0][2].abs() + matrix[1][2].abs();
+        if off <= f64::MIN_POSITIVE {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if matrix[p][q].abs() <= f64::MIN_POSITIVE {
+                continue;
+            }
+            // the rotation annihilating the off-diagonal element at `(p, q)`
+            let theta = (matrix[q][q] - matrix[p][p]) / (2f64 * matrix[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+            let c = 1f64 / (t * t + 1f64).sqrt();
+            let s = t * c;
+            let mut rotation = [[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]];
+            rotation[p][p] = c;
+            rotation[q][q] = c;
+            rotation[p][q] = s;
+            rotation[q][p] = -s;
+            // applies the rotation on both sides of the matrix and accumulates it into the eigenvectors
+            let product = |a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]| {
+                let mut result = [[0f64; 3]; 3];
+                for i in 0..3 {
+                    for j in 0..3 {
+                        result[i][j] = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+                    }
+                }
+                result
+            };
+            let transposed = [
+                [rotation[0][0], rotation[1][0], rotation[2][0]],
+                [rotation[0][1], rotation[1][1], rotation[2][1]],
+                [rotation[0][2], rotation[1][2], rotation[2][2]],
+            ];
+            matrix = product(&product(&transposed, &matrix), &rotation);
+            vectors = product(&vectors, &rotation);
+        }
+    }
+
+    ([matrix[0][0], matrix[1][1], matrix[2][2]], vectors)
+}
+
+/// Computes the interior dihedral angle within `[0, π]` between the adjacent `poly1` and `poly2`, such that two
+/// polygons lying flat on the same plane form an angle of `π`.
+///
+/// Since polygons are always oriented upwards, their normals are made consistent first, namely such that the two
+/// polygons walk along their shared side in opposite directions. Nothing is returned when the polygons do not share
+/// any side.
+pub fn dihedral_angle(poly1: &Polygon, poly2: &Polygon) -> Option<f64> {
+    if !poly1.shares_sides_with(poly2) {
+        return None;
+    }
+    // polygons walking along the shared side in the same direction have inconsistent normals
+    let consistent = !poly1
+        .edges()
+        .any(|side| poly2.edges().any(|other| other == side));
+    let normal = if consistent {
+        poly2.normal()
+    } else {
+        poly2.normal().scale(-1f64)
+    };
+    let angle = poly1.normal().angle_with(&normal);
+
+    (!angle.is_nan()).then_some(std::f64::consts::PI - angle)
+}
+
+/// Computes the convex hull of `points` projected on the xy plane through the Andrew's monotone chain algorithm.
+///
+/// The hull is given counterclockwise, without repeating the closing vertex and starting from the lowest vertex by
+/// x and then by y, while collinear vertices along its sides are discarded. Less than three distinct projected
+/// points are returned as they are.
+pub fn convex_hull_2d(points: &[Point]) -> Vec<Point> {
+    // sorts the points by their projection while discarding the duplicated ones
+    let mut sorted = points.to_vec();
+    sorted.sort();
+    sorted.dedup_by(|a, b| a.x == b.x && a.y == b.y);
+    if sorted.len() < 3 {
+        return points.to_vec();
+    }
+    // whether `c` turns left, namely counterclockwise, after walking from `a` to `b`
+    let left = |a: &Point, b: &Point, c: &Point| {
+        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x) > 0f64
+    };
+    let mut hull = Vec::<Point>::with_capacity(2 * sorted.len());
+    // constructs the lower chain from left to right
+    for point in &sorted {
+        while hull.len() >= 2 && !left(&hull[hull.len() - 2], &hull[hull.len() - 1], point) {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // then the upper chain from right to left without popping the vertices of the lower one
+    let bottom = hull.len() + 1;
+    for point in sorted.iter().rev().skip(1) {
+        while hull.len() >= bottom && !left(&hull[hull.len() - 2], &hull[hull.len() - 1], point) {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // the last vertex closes the hull on the first one
+    hull.pop();
+
+    hull
+}
+
+/// Computes the distance between the parallel planes `normal · p = d1` and `normal · p = d2`, measured along
+/// `normal`.
+///
+/// This amounts to `(d2 - d1).abs()` for a unit normal, but the normals of the crate are not normalised, for
+/// instance [Polygon::normal](super::polygon::Polygon::normal) is as long as twice the area, thus the offsets are
+/// divided by the length of `normal`. Sharing the same normal the planes are parallel by construction, thus
+/// nothing is returned only when `normal` is the zero vector and describes no plane at all.
+#[allow(dead_code)]
+pub(super) fn distance_between_parallel_planes(normal: &Vector, d1: f64, d2: f64) -> Option<f64> {
+    let norm = normal.norm();
+    (norm > f64::EPSILON).then(|| (d2 - d1).abs() / norm)
+}
+
+/// Constructs two unit vectors orthogonal to each other and to the unit `normal`, such that they describe a right
+/// handed frame together with `normal`.
+pub fn orthogonal_basis(normal: Vector) -> (Vector, Vector) {
+    // picks the world axis which is farthest from being parallel to the normal
+    let axis = if normal.x.abs() < 0.9 {
+        Vector {
+            x: 1f64,
+            y: 0f64,
+            z: 0f64,
+        }
+    } else {
+        Vector {
+            x: 0f64,
+            y: 1f64,
+            z: 0f64,
+        }
+    };
+    // the first tangent vector is orthogonal to both the axis and the normal
+    let u = axis.cross(&normal).normalize();
+    // the second tangent vector completes the frame
+    (u, normal.cross(&u))
+}
+
+/// Computes the signed area of the polygon enclosed by the open sequence of `vertices` projected on the xy plane,
+/// which is positive when they are ordered counterclockwise.
+pub(super) fn signed_area_2d(vertices: &[Point]) -> f64 {
+    (0..vertices.len())
+        .map(|index| {
+            let (a, b) = (vertices[index], vertices[(index + 1) % vertices.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2f64
+}
+
+/// Clips the polygon enclosed by the open sequence of `vertices` against the half-plane on the left of the oriented
+/// line through `edge`, both projected on the xy plane, as a single step of the Sutherland-Hodgman algorithm.
+///
+/// The elevation of the vertices introduced on the clipping line is interpolated along the clipped sides, so that
+/// they still lie on the polygon's plane.
+pub(super) fn clip(vertices: &[Point], edge: &Segment) -> Vec<Point> {
+    // positive when `point` lies on the left of `edge` on the xy plane
+    let side = |point: &Point| {
+        (edge.1.x - edge.0.x) * (point.y - edge.0.y) - (edge.1.y - edge.0.y) * (point.x - edge.0.x)
+    };
+    // the clipped sequence of vertices
+    let mut clipped = Vec::<Point>::with_capacity(vertices.len() + 1);
+    // visits each side of the polygon from `previous` to `current`
+    for (index, current) in vertices.iter().enumerate() {
+        let previous = &vertices[(index + vertices.len() - 1) % vertices.len()];
+        let (alpha, beta) = (side(previous), side(current));
+        // the side crosses the clipping line thus the intersection is kept
+        if (alpha >= 0f64) != (beta >= 0f64) {
+            clipped.push(previous.lerp_3d(current, alpha / (alpha - beta)));
+        }
+        // vertices inside the half-plane are kept
+        if beta >= 0f64 {
+            clipped.push(*current);
+        }
+    }
+
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distance_between_parallel_planes, intersect_segments, Point, Vector};
+
+    #[test]
+    fn slerp() {
+        let a = Vector {
+            x: 1f64,
+            y: 0f64,
+            z: 0f64,
+        };
+        let b = Vector {
+            x: 0f64,
+            y: 1f64,
+            z: 0f64,
+        };
+        let half = a.slerp(&b, 0.5);
+        assert!(
+            (half.x - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12
+                && (half.y - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12
+                && half.z.abs() < 1e-12,
+            "Spherical interpolation halfway between two orthogonal unit vectors lies on the bisector."
+        );
+        let other = a.slerp(&b, 0.3);
+        assert!(
+            (other.norm() - 1f64).abs() < 1e-12,
+            "Spherical interpolation preserves unit length."
+        );
+        // opposite vectors
+        let opposite = a.scale(-1f64);
+        for t in [0f64, 0.3, 0.5, 1f64] {
+            assert!(
+                (a.slerp(&opposite, t).norm() - 1f64).abs() < 1e-12,
+                "Spherical interpolation between opposite vectors preserves unit length."
+            );
+        }
+        assert!(a.slerp(&opposite, 0.5).dot(&a).abs() < 1e-12);
+        assert!((a.slerp(&opposite, 1f64).dot(&opposite) - 1f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn intersect_segments_crossing() {
+        let point = |x: f64, y: f64, z: f64| Point { x, y, z };
+        let a = (point(0f64, 0f64, 0f64), point(2f64, 2f64, 2f64));
+        let b = (point(0f64, 2f64, 1f64), point(2f64, 0f64, 1f64));
+        let crossing = intersect_segments(a, b, 1e-9).unwrap();
+        assert!(crossing.distance(&point(1f64, 1f64, 1f64)) < 1e-12);
+        assert!(
+            intersect_segments(a, (point(1f64, 0f64, 0f64), point(3f64, 2f64, 2f64)), 1e-9)
+                .is_none(),
+            "Parallel segments never meet."
+        );
+        let skew = (point(0f64, 2f64, 1.5), point(2f64, 0f64, 1.5));
+        assert!(
+            intersect_segments(a, skew, 1e-9).is_none(),
+            "Skew segments do not meet."
+        );
+        assert!(
+            intersect_segments(a, skew, 1f64).is_some(),
+            "Unless they are close enough."
+        );
+        assert!(
+            intersect_segments(a, (point(2f64, 4f64, 1f64), point(4f64, 2f64, 1f64)), 1e-9)
+                .is_none(),
+            "The lines meet outside of the segments."
+        );
+    }
+
+    #[test]
+    fn distance_between_parallel_planes_offsets() {
+        let normal = Vector {
+            x: 0f64,
+            y: 3f64,
+            z: 4f64,
+        };
+        assert_eq!(
+            Some(10f64),
+            distance_between_parallel_planes(&normal.normalize(), -5f64, 5f64)
+        );
+        assert_eq!(
+            Some(2f64),
+            distance_between_parallel_planes(&normal, -5f64, 5f64),
+            "The offsets are scaled by the length of the normal."
+        );
+        assert_eq!(
+            None,
+            distance_between_parallel_planes(&Vector::zero(), -5f64, 5f64)
+        );
+    }
+}
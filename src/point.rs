@@ -1,14 +1,157 @@
 /// Three dimensional point
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-/// Oriented segment connecting two [Point]s.
+impl Point {
+    /// Constructs the point from its `[x, y, z]` coordinates.
+    pub fn from_array(coordinates: [f64; 3]) -> Self {
+        Self::from(coordinates)
+    }
+
+    /// Computes the Euclidean distance from `other`.
+    pub fn distance(&self, other: &Point) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Computes the squared Euclidean distance from `other`, which spares the square root when only comparing.
+    pub fn distance_squared(&self, other: &Point) -> f64 {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2)
+    }
+
+    /// Projects the point orthogonally onto `s`, clamping the projection within its endpoints.
+    pub fn project_onto_segment(&self, s: Segment) -> Point {
+        super::plane::segment_closest_point(s, *self)
+    }
+
+    /// Linearly interpolates towards `other` where `t = 0` yields exactly `self` and `t = 1` yields exactly `other`,
+    /// while extrapolating along the same line for any other `t` outside `[0, 1]`.
+    pub fn lerp(&self, other: &Point, t: f64) -> Point {
+        Point {
+            x: self.x * (1f64 - t) + other.x * t,
+            y: self.y * (1f64 - t) + other.y * t,
+            z: self.z * (1f64 - t) + other.z * t,
+        }
+    }
+
+    /// Like [Point::lerp] but clamps `t` within `[0, 1]` so that the result always lies between the two points.
+    pub fn lerp_clamped(&self, other: &Point, t: f64) -> Point {
+        self.lerp(other, t.clamp(0f64, 1f64))
+    }
+
+    /// Linearly interpolates towards `other` on the xy plane while keeping the elevation of `self`.
+    pub fn lerp_2d(&self, other: &Point, t: f64) -> Point {
+        Point {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            z: self.z,
+        }
+    }
+
+    /// Linearly interpolates towards `other` where `t = 0` yields `self` and `t = 1` yields `other`, see [Point::lerp].
+    pub fn lerp_3d(&self, other: &Point, t: f64) -> Point {
+        self.lerp(other, t)
+    }
+}
+
+/// Oriented segment connecting two [Point]s, serialized as a two-element array when the `serde` feature is enabled.
 pub type Segment = (Point, Point);
 
+/// Yields the canonical form of `s` where the lesser endpoint comes first, so that both orientations of the same
+/// segment share a single representation.
+pub fn segment_canonical(s: Segment) -> Segment {
+    if s.0 <= s.1 {
+        s
+    } else {
+        (s.1, s.0)
+    }
+}
+
+/// Checks whether `a` and `b` connect the same endpoints regardless of their orientation.
+pub fn segment_canonical_eq(a: Segment, b: Segment) -> bool {
+    segment_canonical(a) == segment_canonical(b)
+}
+
+/// Removes the duplicated `segments`, including those given in both orientations, while keeping the first
+/// occurrence of each segment in its canonical form, see [segment_canonical].
+pub fn deduplicate_segments(segments: &[Segment]) -> Vec<Segment> {
+    let mut seen = hashbrown::HashSet::<Segment>::new();
+
+    segments
+        .iter()
+        .map(|&segment| segment_canonical(segment))
+        .filter(|&segment| seen.insert(segment))
+        .collect()
+}
+
+/// The defects of an input segment detected by [validate_segments].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentError {
+    /// Both endpoints of the segment are the same point.
+    ZeroLength(Segment),
+    /// Some coordinate of the segment is either infinite or `NaN`.
+    NonFinite(Segment),
+}
+
+impl std::fmt::Display for SegmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SegmentError::ZeroLength((u, v)) => {
+                write!(f, "segment from {} to {} has zero length", u, v)
+            }
+            SegmentError::NonFinite((u, v)) => {
+                write!(f, "segment from {} to {} has non-finite coordinates", u, v)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SegmentError {}
+
+/// Checks that `segments` are suitable to construct polygons, namely that their coordinates are finite and that
+/// their endpoints differ, otherwise it yields the errors of all faulty segments.
+pub fn validate_segments(segments: &[Segment]) -> Result<(), Vec<SegmentError>> {
+    let errors = segments
+        .iter()
+        .filter_map(|&(u, v)| {
+            if [u.x, u.y, u.z, v.x, v.y, v.z]
+                .iter()
+                .any(|value| !value.is_finite())
+            {
+                Some(SegmentError::NonFinite((u, v)))
+            } else if u == v {
+                Some(SegmentError::ZeroLength((u, v)))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<SegmentError>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Computes the Euclidean distance between the endpoints of `s`.
+pub fn segment_length(s: Segment) -> f64 {
+    s.0.distance(&s.1)
+}
+
+/// Computes the point halfway between the endpoints of `s`.
+pub fn segment_midpoint(s: Segment) -> Point {
+    Point {
+        x: (s.0.x + s.1.x) / 2f64,
+        y: (s.0.y + s.1.y) / 2f64,
+        z: (s.0.z + s.1.z) / 2f64,
+    }
+}
+
 impl PartialEq for Point {
     /// Equality between points is given by their coordinates
     fn eq(&self, other: &Self) -> bool {
@@ -45,6 +188,52 @@ impl PartialOrd for Point {
     }
 }
 
+impl std::fmt::Display for Point {
+    /// Formats the point as `(x, y, z)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+}
+
+impl std::fmt::Debug for Point {
+    /// Formats the point as `Point { x, y, z }`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Point")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
+    }
+}
+
+impl From<[f64; 3]> for Point {
+    /// Constructs the point from its `[x, y, z]` coordinates.
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Point { x, y, z }
+    }
+}
+
+impl From<Point> for [f64; 3] {
+    /// Yields the `[x, y, z]` coordinates of the point.
+    fn from(point: Point) -> Self {
+        [point.x, point.y, point.z]
+    }
+}
+
+impl From<(f64, f64, f64)> for Point {
+    /// Constructs the point from its `(x, y, z)` coordinates.
+    fn from((x, y, z): (f64, f64, f64)) -> Self {
+        Point { x, y, z }
+    }
+}
+
+impl From<Point> for (f64, f64, f64) {
+    /// Yields the `(x, y, z)` coordinates of the point.
+    fn from(point: Point) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
 impl std::hash::Hash for Point {
     /// Hashing is based on the coordinates' bits
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
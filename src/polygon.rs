@@ -1,9 +1,37 @@
-use super::point::Point;
+use super::{
+    plane::Vector,
+    point::{Point, Segment},
+};
 
-use hashbrown::HashSet;
-use std::collections::BTreeSet;
+use hashbrown::{HashMap, HashSet};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// The rotational sense of the vertices of a polygon when looking down from above the xy plane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindingOrder {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// The error raised when vertices cannot describe a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    /// Less than three distinct vertices are given or they are all collinear.
+    Degenerate,
+}
+
+impl std::fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolygonError::Degenerate => write!(f, "the vertices do not describe a polygon"),
+        }
+    }
+}
+
+impl std::error::Error for PolygonError {}
 
 /// A polygon is represented by an ordered set of vertices.
+#[derive(Clone)]
 pub struct Polygon {
     /// Unique set of vertices belonging to the polygon.
     set: BTreeSet<Point>,
@@ -32,6 +60,26 @@ impl Polygon {
         }
     }
 
+    /// Like [Polygon::from] but first discards consecutive repeated vertices, also across the closing side, and
+    /// then validates the outcome.
+    ///
+    /// No polygon is constructed when less than three vertices are left or when they are all collinear.
+    pub fn from_points_with_validation(mut vertices: Vec<Point>) -> Option<Self> {
+        // removes consecutive duplicates
+        vertices.dedup();
+        // removes the closing vertices which repeat the opening one
+        while vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        // at least a triangle is required
+        if vertices.len() < 3 {
+            return None;
+        }
+        // collinear vertices do not describe any plane
+        let polygon = Self::from(vertices);
+        (polygon.normal().norm() > f64::EPSILON).then_some(polygon)
+    }
+
     /// Constructs the bounding box around the polygon.
     fn boundary(vertices: &[Point]) -> (Point, Point) {
         // minimum point according to the three dimensions
@@ -101,7 +149,7 @@ impl Polygon {
     }
 
     /// Checks whether the polygon shares sides with `other`.
-    fn shares_sides_with(&self, other: &Self) -> bool {
+    pub(super) fn shares_sides_with(&self, other: &Self) -> bool {
         for i in 0..(self.sequence.len() - 1) {
             for j in 0..(other.sequence.len() - 1) {
                 if (self.sequence[i], self.sequence[i + 1])
@@ -126,18 +174,865 @@ impl Polygon {
                 .all(|point| self.contains_point(point))
     }
 
+    /// Computes the normal vector of the polygon's plane whose magnitude equals twice the [Polygon::area].
+    ///
+    /// The z-component of the normal is never negative because [Polygon::from] orders the vertices accordingly, unless
+    /// the polygon has been flipped through [Polygon::flip]. The normal of a degenerate polygon is zero, see
+    /// [Polygon::is_degenerate].
+    pub fn normal(&self) -> Vector {
+        let normal = super::plane::normal(&self.sequence);
+
+        if self.degenerate(&normal) {
+            Vector::zero()
+        } else {
+            normal
+        }
+    }
+
+    /// Checks whether the polygon is structurally invalid, namely when it has less than three distinct vertices or
+    /// when they are collinear such that its [Polygon::area] vanishes.
+    ///
+    /// Unlike the area threshold of [filter], tiny but valid polygons are not degenerate.
+    pub fn is_degenerate(&self) -> bool {
+        self.degenerate(&super::plane::normal(&self.sequence))
+    }
+
+    /// Like [Polygon::is_degenerate] but reuses the `normal` computed on the vertices.
+    fn degenerate(&self, normal: &Vector) -> bool {
+        self.vertex_count() < 3 || normal.norm() / 2f64 < f64::EPSILON
+    }
+
+    /// Checks whether the polygon is strictly convex once projected on its own plane.
+    ///
+    /// Polygons with collinear consecutive vertices or with less than three distinct vertices are conservatively
+    /// considered not convex.
+    pub fn is_convex(&self) -> bool {
+        // the number of vertices excluding the closing one
+        let n = self.sequence.len() - 1;
+        // the frame of the polygon's plane
+        let normal = self.normal().normalize();
+        // degenerate polygons are not convex
+        if self.set.len() < 3 || normal.norm() <= f64::EPSILON {
+            return false;
+        }
+        let (u, v) = super::plane::orthogonal_basis(normal);
+        // the projected direction of each side
+        let directions = self
+            .sequence
+            .windows(2)
+            .map(|side| {
+                let vector = Vector::between(&(side[0], side[1]));
+                (vector.dot(&u), vector.dot(&v))
+            })
+            .collect::<Vec<(f64, f64)>>();
+        // the signed turning angle at the end of each side, positive when turning counterclockwise
+        let turns = (0..n)
+            .map(|index| {
+                let ((ax, ay), (bx, by)) = (directions[index], directions[(index + 1) % n]);
+                (ax * by - ay * bx).atan2(ax * bx + ay * by)
+            })
+            .collect::<Vec<f64>>();
+        // every turn must be counterclockwise and the boundary must wind exactly once
+        turns.iter().all(|&turn| turn > 0f64)
+            && (turns.iter().sum::<f64>() - 2f64 * std::f64::consts::PI).abs() < 1e-6
+    }
+
+    /// Checks whether every vertex lies within `tolerance` from the plane passing through the [Polygon::centroid]
+    /// with direction [Polygon::normal].
+    ///
+    /// Degenerate polygons whose vertices are all collinear do not describe any plane thus they are not planar.
+    pub fn is_planar(&self, tolerance: f64) -> bool {
+        let normal = self.normal().normalize();
+        // a zero normal does not describe any plane
+        if normal.norm() <= f64::EPSILON {
+            return false;
+        }
+        // the plane passes through the centroid
+        let centroid = self.centroid();
+
+        self.sequence.iter().all(|vertex| {
+            super::plane::distance_point_to_plane(vertex, &centroid, &normal).abs() <= tolerance
+        })
+    }
+
+    /// Computes the rotational sense of the vertices projected on the xy plane when looking down from above.
+    ///
+    /// Since [Polygon::from] guarantees a normal pointing upwards, this is [WindingOrder::CounterClockwise] unless the
+    /// polygon has been flipped through [Polygon::flip]. Vertical polygons whose projection has no area are always
+    /// considered counterclockwise.
+    pub fn winding_order(&self) -> WindingOrder {
+        if super::plane::signed_area_2d(&self.sequence[1..]) < 0f64 {
+            WindingOrder::Clockwise
+        } else {
+            WindingOrder::CounterClockwise
+        }
+    }
+
+    /// Constructs a new polygon with the same vertices in reversed order, thus with opposite normal.
+    pub fn flip(&self) -> Polygon {
+        Polygon {
+            set: self.set.clone(),
+            sequence: self.sequence.iter().rev().copied().collect(),
+            boundary: self.boundary,
+        }
+    }
+
+    /// Like [Polygon::flip] but reverses the order of the vertices in-place.
+    pub fn flip_in_place(&mut self) {
+        // the closing vertex still repeats the opening one
+        self.sequence.reverse();
+    }
+
+    /// Checks whether the angle between the polygon's normal and the z-axis is less than `tolerance` radians.
+    ///
+    /// Degenerate polygons with a zero normal are neither horizontal nor vertical.
+    pub fn is_horizontal(&self, tolerance: f64) -> bool {
+        // comparisons with a `NaN` slope are always false
+        self.slope_angle() < tolerance
+    }
+
+    /// Checks whether the angle between the polygon's normal and the z-axis is within `tolerance` radians from a
+    /// right angle.
+    ///
+    /// Degenerate polygons with a zero normal are neither horizontal nor vertical.
+    pub fn is_vertical(&self, tolerance: f64) -> bool {
+        // comparisons with a `NaN` slope are always false
+        std::f64::consts::FRAC_PI_2 - self.slope_angle() <= tolerance
+    }
+
+    /// Computes the inclination of the polygon from the horizontal plane as the angle in radians between its normal
+    /// and the z-axis, which lies within `[0, π/2]`.
+    ///
+    /// Degenerate polygons with a zero normal have no inclination thus `NaN` is returned.
+    pub fn slope_angle(&self) -> f64 {
+        let normal = self.normal();
+        let norm = normal.norm();
+        // the absolute value also accounts for flipped polygons
+        if norm <= f64::EPSILON {
+            f64::NAN
+        } else {
+            (normal.z.abs() / norm).min(1f64).acos()
+        }
+    }
+
     /// Assuming the polygon is quasi-bidimensional, computes the area on its plane.
-    fn area(&self) -> f64 {
-        super::plane::normal(&self.sequence).norm() / 2f64
+    pub fn area(&self) -> f64 {
+        self.normal().norm() / 2f64
     }
 
     /// Projects the polygon on the xy plane and computes its area (from above).
-    fn area_projected(&self) -> f64 {
-        super::plane::normal(&self.sequence).z.abs() / 2f64
+    pub fn area_projected(&self) -> f64 {
+        self.normal().z.abs() / 2f64
+    }
+
+    /// Yields the precomputed bounding box of the polygon as `(min_corner, max_corner)` on the xy plane.
+    ///
+    /// Note that the z-coordinates of both corners are `NaN` since the bounding box is only two dimensional, while
+    /// the elevation range is given by [Polygon::elevation_range].
+    pub fn bounding_box(&self) -> (Point, Point) {
+        self.boundary
+    }
+
+    /// Computes the minimum and maximum elevations of the vertices as `(min_z, max_z)`.
+    ///
+    /// The range is computed on the fly and the closing vertex is not double-counted.
+    pub fn elevation_range(&self) -> (f64, f64) {
+        self.sequence[1..]
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), vertex| {
+                (min.min(vertex.z), max.max(vertex.z))
+            })
+    }
+
+    /// Counts the vertices of the polygon, excluding the closing one which repeats the first.
+    pub fn vertex_count(&self) -> usize {
+        self.sequence.len() - 1
+    }
+
+    /// Counts the sides of the polygon, including the closing one.
+    ///
+    /// The polygon is always closed thus `vertex_count() == edge_count()`.
+    pub fn edge_count(&self) -> usize {
+        self.sequence.len() - 1
+    }
+
+    /// Computes the perimeter of the polygon as the total length of its sides in space.
+    pub fn perimeter(&self) -> f64 {
+        // the closing side is included exactly once since `sequence.first() == sequence.last()`
+        self.edges().map(|side| Vector::between(&side).norm()).sum()
+    }
+
+    /// Computes the area-weighted centroid of the polygon lying on its plane.
+    ///
+    /// The polygon is decomposed in a fan of triangles around its unweighted center and the centroids of the
+    /// triangles are averaged using their signed areas as weights. Unlike the mean of the vertices, the result does
+    /// not depend on how densely the vertices are distributed along the boundary, while the two agree on regular
+    /// polygons.
+    pub fn centroid(&self) -> Point {
+        // the unweighted center is the apex of the triangles fan and reduces big coordinates values
+        let center = super::plane::center(&self.sequence);
+        // signed areas are measured along the normal to account for concave polygons
+        let normal = self.normal();
+        // accumulates the weighted centroids of the triangles with respect to `center`
+        let (weighted, total) = (0..(self.sequence.len() - 1))
+            .map(|index| {
+                let a = Vector::from(&self.sequence[index]).subtract(&center);
+                let b = Vector::from(&self.sequence[index + 1]).subtract(&center);
+                let weight = a.cross(&b).dot(&normal);
+                (a.add(&b).scale(weight / 3f64), weight)
+            })
+            .fold(
+                (Vector::zero(), 0f64),
+                |(vector, total), (centroid, weight)| (vector.add(&centroid), total + weight),
+            );
+        // degenerate polygons without area fall back to the unweighted center
+        let centroid = if total.abs() <= f64::EPSILON {
+            center
+        } else {
+            center.add(&weighted.scale(1f64 / total))
+        };
+        Point {
+            x: centroid.x,
+            y: centroid.y,
+            z: centroid.z,
+        }
+    }
+
+    /// Computes the signed distance between the polygon's centroid and the plane `normal · p = d` where `normal`
+    /// is expected to be a unit vector.
+    pub fn signed_distance_to_plane(&self, normal: &Vector, d: f64) -> f64 {
+        normal.dot(&Vector::from(&self.centroid())) - d
+    }
+
+    /// Checks whether all the vertices lie in the half-space where `normal · p >= d` and the polygon does not
+    /// entirely lie on the plane.
+    pub fn on_positive_side_of_plane(&self, normal: &Vector, d: f64) -> bool {
+        self.sequence
+            .iter()
+            .all(|vertex| normal.dot(&Vector::from(vertex)) >= d)
+            && self
+                .sequence
+                .iter()
+                .any(|vertex| normal.dot(&Vector::from(vertex)) > d)
+    }
+
+    /// Checks whether all the vertices lie in the half-space where `normal · p <= d` and the polygon does not
+    /// entirely lie on the plane.
+    pub fn on_negative_side_of_plane(&self, normal: &Vector, d: f64) -> bool {
+        self.sequence
+            .iter()
+            .all(|vertex| normal.dot(&Vector::from(vertex)) <= d)
+            && self
+                .sequence
+                .iter()
+                .any(|vertex| normal.dot(&Vector::from(vertex)) < d)
+    }
+
+    /// Splits the polygon with the plane `normal · p = d` into the part lying in front of it, namely where
+    /// `normal · p >= d`, and the part lying behind it.
+    ///
+    /// Vertices whose distance from the plane is within `tolerance` are considered lying on the plane and are
+    /// shared by both parts. Either part is missing when it would have less than three vertices.
+    pub fn split_by_plane(
+        &self,
+        normal: &Vector,
+        d: f64,
+        tolerance: f64,
+    ) -> (Option<Polygon>, Option<Polygon>) {
+        // vertices of the two parts in the same order as the original polygon
+        let mut front = Vec::<Point>::new();
+        let mut back = Vec::<Point>::new();
+        // signed distance of each vertex from the plane
+        let distance = |vertex: &Point| normal.dot(&Vector::from(vertex)) - d;
+        // walks each side of the polygon and assigns its starting vertex to one or both parts
+        for index in 0..(self.sequence.len() - 1) {
+            let (a, b) = (self.sequence[index], self.sequence[index + 1]);
+            let (alpha, beta) = (distance(&a), distance(&b));
+
+            if alpha >= -tolerance {
+                front.push(a);
+            }
+
+            if alpha <= tolerance {
+                back.push(a);
+            }
+            // the side crosses the plane so the intersection point belongs to both parts
+            if (alpha > tolerance && beta < -tolerance) || (alpha < -tolerance && beta > tolerance)
+            {
+                let intersection = a.lerp_3d(&b, alpha / (alpha - beta));
+                front.push(intersection);
+                back.push(intersection);
+            }
+        }
+        // only proper polygons are constructed
+        let construct =
+            |vertices: Vec<Point>| (vertices.len() >= 3).then(|| Polygon::from(vertices));
+        (construct(front), construct(back))
+    }
+
+    /// Computes the shortest distance between `point` and the polygon, considering both its interior and boundary.
+    ///
+    /// When the projection of `point` on the polygon's plane falls inside the polygon, the distance is measured from
+    /// the plane, otherwise it is measured from the closest side.
+    pub fn distance_to_point(&self, point: &Point) -> f64 {
+        // the distance from the closest side is the fallback for any query point
+        let boundary = || {
+            self.edges()
+                .map(|side| super::plane::segment_distance_to_point(side, *point))
+                .fold(f64::INFINITY, f64::min)
+        };
+        // degenerate polygons without a plane only have their boundary
+        let normal = self.normal().normalize();
+
+        if normal.norm() <= f64::EPSILON {
+            return boundary();
+        }
+        // the frame of the polygon's plane centered on the first vertex
+        let (u, v) = super::plane::orthogonal_basis(normal);
+        let origin = self.sequence[0];
+        let project = |vertex: &Point| {
+            let vector = Vector::between(&(origin, *vertex));
+            (vector.dot(&u), vector.dot(&v))
+        };
+        // checks whether the projected query point lies inside the projected polygon
+        let (x, y) = project(point);
+        let mut inside = false;
+
+        for side in self.sequence.windows(2) {
+            let ((ax, ay), (bx, by)) = (project(&side[0]), project(&side[1]));
+
+            if (ay > y) != (by > y) && x < ax + (y - ay) * (bx - ax) / (by - ay) {
+                inside = !inside;
+            }
+        }
+
+        if inside {
+            super::plane::distance_point_to_plane(point, &origin, &normal).abs()
+        } else {
+            boundary()
+        }
+    }
+
+    /// Checks whether the polygon and `other`, projected on the xy plane, share any interior point, either because
+    /// they partially overlap or because one encloses the other.
+    ///
+    /// Polygons merely touching along their boundaries do not intersect, as well as vertical polygons whose
+    /// projection has no interior.
+    pub fn intersects(&self, other: &Polygon) -> bool {
+        // fast path when the bounding boxes do not overlap
+        if self.boundary.1.x <= other.boundary.0.x
+            || self.boundary.0.x >= other.boundary.1.x
+            || self.boundary.1.y <= other.boundary.0.y
+            || self.boundary.0.y >= other.boundary.1.y
+        {
+            return false;
+        }
+        // twice the signed area of the projected triangle, positive when counterclockwise
+        let orientation =
+            |a: &Point, b: &Point, c: &Point| (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+        // two sides crossing each other at interior points means that the polygons partially overlap
+        let crossing = self.edges().any(|(a, b)| {
+            other.edges().any(|(c, d)| {
+                orientation(&a, &b, &c) * orientation(&a, &b, &d) < 0f64
+                    && orientation(&c, &d, &a) * orientation(&c, &d, &b) < 0f64
+            })
+        });
+
+        if crossing {
+            return true;
+        }
+        // otherwise one polygon might enclose the other, which is detected by checking whether any interior point
+        // of a polygon, namely the centroids of its triangles, lies inside the other one
+        let encloses = |outer: &Polygon, inner: &Polygon| {
+            inner.triangulate().into_iter().any(|[a, b, c]| {
+                // skips degenerate triangles whose centroid is not interior
+                orientation(&a, &b, &c).abs() > f64::EPSILON
+                    && outer.contains_point(&Point {
+                        x: (a.x + b.x + c.x) / 3f64,
+                        y: (a.y + b.y + c.y) / 3f64,
+                        z: (a.z + b.z + c.z) / 3f64,
+                    })
+            })
+        };
+
+        encloses(self, other) || encloses(other, self)
+    }
+
+    /// Computes the ratio between the [Polygon::area] and the [Polygon::area_projected], which is `1` for
+    /// horizontal polygons and grows as `1 / cos(slope)` with the [Polygon::slope_angle].
+    ///
+    /// Polygons without projected area, like vertical ones, have an infinite ratio.
+    pub fn surface_area_ratio(&self) -> f64 {
+        let projected = self.area_projected();
+
+        if projected == 0f64 {
+            f64::INFINITY
+        } else {
+            self.area() / projected
+        }
+    }
+
+    /// Constructs a new polygon by scaling the vertices around the [Polygon::centroid] by `factor`, so that the area
+    /// gets scaled by the square of `factor`.
+    pub fn scale_around_centroid(&self, factor: f64) -> Polygon {
+        let centroid = self.centroid();
+        // the closing vertex is skipped because it is replicated by the construction
+        Polygon::from(
+            self.sequence[1..]
+                .iter()
+                .map(|vertex| centroid.lerp_3d(vertex, factor))
+                .collect(),
+        )
+    }
+
+    /// Computes the volume enclosed between the polygon and the horizontal plane at elevation `z`, considering only
+    /// the parts of the polygon lying below such plane.
+    ///
+    /// The polygon is decomposed in a fan of triangles, each one is clipped at elevation `z` and the prismatoids
+    /// between the remaining parts and the horizontal plane are summed up. The volume is the same whatever the
+    /// orientation of the polygon, see [Polygon::flip], and vertical polygons enclose no volume at all.
+    pub fn volume_below(&self, z: f64) -> f64 {
+        // signed area of a triangle projected on the xy plane, positive when counterclockwise
+        let area = |a: &Point, b: &Point, c: &Point| {
+            ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2f64
+        };
+        // the apex of the triangles fan
+        let root = self.sequence[0];
+
+        self.sequence[1..]
+            .windows(2)
+            .map(|pair| {
+                let triangle = [root, pair[0], pair[1]];
+                // the part of the triangle lying below `z`
+                let mut below = Vec::<Point>::with_capacity(4);
+
+                for index in 0..triangle.len() {
+                    let (a, b) = (triangle[index], triangle[(index + 1) % triangle.len()]);
+
+                    if a.z <= z {
+                        below.push(a);
+                    }
+                    // the side crosses the horizontal plane
+                    if (a.z < z && b.z > z) || (a.z > z && b.z < z) {
+                        below.push(a.lerp_3d(&b, (z - a.z) / (b.z - a.z)));
+                    }
+                }
+                // sums the prisms between each triangle of the clipped part and the horizontal plane
+                (1..below.len().saturating_sub(1))
+                    .map(|index| {
+                        let (a, b, c) = (below[0], below[index], below[index + 1]);
+                        area(&a, &b, &c) * (z - (a.z + b.z + c.z) / 3f64)
+                    })
+                    .sum::<f64>()
+            })
+            .sum::<f64>()
+            // the signed areas follow the orientation of the polygon, which does not affect the volume
+            .abs()
+    }
+
+    /// Computes the convex hull of the vertices on the polygon's own plane, see [super::plane::convex_hull_2d],
+    /// whose vertices are a subset of the polygon's ones.
+    ///
+    /// The vertices of a non-planar polygon are projected on the plane of its normal, whereas a degenerate polygon is
+    /// returned as it is.
+    pub fn convex_hull(&self) -> Polygon {
+        if self.is_degenerate() {
+            return self.clone();
+        }
+        // the frame of the polygon's plane with origin at its first vertex
+        let (u, v) = super::plane::orthogonal_basis(self.normal().normalize());
+        let origin = self.sequence[0];
+        // projects each vertex on the plane while remembering where it comes from
+        let projections = self.sequence[..(self.sequence.len() - 1)]
+            .iter()
+            .map(|&vertex| {
+                let offset = Vector::between(&(origin, vertex));
+                (
+                    Point {
+                        x: offset.dot(&u),
+                        y: offset.dot(&v),
+                        z: 0f64,
+                    },
+                    vertex,
+                )
+            })
+            .collect::<HashMap<Point, Point>>();
+        // lifts the hull back to the original vertices
+        Polygon::from(
+            super::plane::convex_hull_2d(&projections.keys().copied().collect::<Vec<Point>>())
+                .into_iter()
+                .map(|projection| projections[&projection])
+                .collect(),
+        )
+    }
+
+    /// Clips the polygon to the axis-aligned box spanning from `min` to `max` on the xy plane through the
+    /// Sutherland-Hodgman algorithm, interpolating the elevation of the new vertices.
+    ///
+    /// It yields `None` when nothing but a degenerate part of the polygon lies within the box.
+    pub fn clip_to_bbox(&self, min: Point, max: Point) -> Option<Polygon> {
+        // the polygon is left untouched when it lies entirely within the box
+        if self.boundary.0.x >= min.x
+            && self.boundary.0.y >= min.y
+            && self.boundary.1.x <= max.x
+            && self.boundary.1.y <= max.y
+        {
+            return Some(self.clone());
+        }
+
+        intersect_with_aabb(self, &min, &max)
+    }
+
+    /// Computes the area of the intersection between the polygon and `other` when both are projected on the xy
+    /// plane by clipping the former with the latter through the Sutherland-Hodgman algorithm.
+    ///
+    /// Note that the result is exact only when the projection of `other` is convex.
+    pub fn area_of_intersection_xy(&self, other: &Polygon) -> f64 {
+        // fast path when the bounding boxes do not overlap
+        if self.boundary.1.x < other.boundary.0.x
+            || self.boundary.0.x > other.boundary.1.x
+            || self.boundary.1.y < other.boundary.0.y
+            || self.boundary.0.y > other.boundary.1.y
+        {
+            return 0f64;
+        }
+        // the clipping polygon must be counterclockwise, which is impossible when its projection is degenerate
+        let area = super::plane::signed_area_2d(&other.sequence[1..]);
+        if area == 0f64 {
+            return 0f64;
+        }
+        // otherwise a clockwise projection is reversed
+        let sequence = if area < 0f64 {
+            other.sequence.iter().rev().copied().collect()
+        } else {
+            other.sequence.clone()
+        };
+        // clips the polygon, excluding its closing vertex, against each side of `other`
+        let vertices = sequence
+            .windows(2)
+            .fold(self.sequence[1..].to_vec(), |vertices, side| {
+                super::plane::clip(&vertices, &(side[0], side[1]))
+            });
+
+        super::plane::signed_area_2d(&vertices).abs()
+    }
+
+    /// Decomposes the polygon in triangles by ear clipping its projection on its own plane.
+    ///
+    /// Each triangle is given by the indices of its vertices in the sequence of vertices, excluding the closing one,
+    /// and is counterclockwise with respect to the polygon's normal.
+    fn ears(&self) -> Vec<[usize; 3]> {
+        // the number of vertices excluding the closing one
+        let n = self.sequence.len() - 1;
+        // at least a triangle is required
+        if n < 3 {
+            return Vec::new();
+        }
+        // the frame of the polygon's plane
+        let normal = self.normal().normalize();
+        // degenerate polygons without a plane are decomposed as a fan of triangles
+        if normal.norm() <= f64::EPSILON {
+            return (1..(n - 1)).map(|index| [0, index, index + 1]).collect();
+        }
+        let (u, v) = super::plane::orthogonal_basis(normal);
+        // projects the vertices on the plane relatively to the first vertex to reduce big coordinates values
+        let origin = Vector::from(&self.sequence[0]);
+        let projected = self.sequence[..n]
+            .iter()
+            .map(|vertex| {
+                let vector = Vector::from(vertex).subtract(&origin);
+                (vector.dot(&u), vector.dot(&v))
+            })
+            .collect::<Vec<(f64, f64)>>();
+        // twice the signed area of the projected triangle, positive when counterclockwise
+        let orientation = |a: usize, b: usize, c: usize| {
+            let ((ax, ay), (bx, by), (cx, cy)) = (projected[a], projected[b], projected[c]);
+            (bx - ax) * (cy - ay) - (cx - ax) * (by - ay)
+        };
+        // the vertices still to be clipped and the clipped triangles
+        let mut remaining = (0..n).collect::<Vec<usize>>();
+        let mut triangles = Vec::<[usize; 3]>::with_capacity(n - 2);
+
+        while remaining.len() > 3 {
+            let m = remaining.len();
+            // checks whether the vertex at `position` is the tip of an ear
+            let ear = |position: usize| {
+                let (a, b, c) = (
+                    remaining[(position + m - 1) % m],
+                    remaining[position],
+                    remaining[(position + 1) % m],
+                );
+                // the tip must be convex and no other vertex must lie within the triangle
+                orientation(a, b, c) > 0f64
+                    && remaining.iter().all(|&other| {
+                        [a, b, c]
+                            .iter()
+                            .any(|&corner| self.sequence[corner] == self.sequence[other])
+                            || orientation(a, b, other) < 0f64
+                            || orientation(b, c, other) < 0f64
+                            || orientation(c, a, other) < 0f64
+                    })
+            };
+            // numerical degeneracies might leave no ear, in which case the first vertex is clipped anyway
+            let position = (0..m).find(|&position| ear(position)).unwrap_or(0);
+            triangles.push([
+                remaining[(position + m - 1) % m],
+                remaining[position],
+                remaining[(position + 1) % m],
+            ]);
+            remaining.remove(position);
+        }
+        // the last triangle is left
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+        triangles
+    }
+
+    /// Decomposes the polygon in triangles by ear clipping its projection on its own plane, so that the triangles
+    /// tessellate the polygon exactly.
+    ///
+    /// A polygon with `n` vertices produces `n - 2` triangles, while nothing is produced when it is degenerate, see
+    /// [Polygon::is_degenerate].
+    pub fn triangulate(&self) -> Vec<[Point; 3]> {
+        if self.is_degenerate() {
+            return Vec::new();
+        }
+
+        self.ears()
+            .into_iter()
+            .map(|triangle| triangle.map(|index| self.sequence[index]))
+            .collect()
+    }
+
+    /// Constructs the indexed triangle mesh of the polygon, that is the buffer of unique vertices and the buffer of
+    /// triangles referencing them through 0-based indices.
+    ///
+    /// The polygon is decomposed by ear clipping and `n` vertices always produce `n - 2` triangles.
+    pub fn to_indexed_mesh(&self) -> (Vec<Point>, Vec<[usize; 3]>) {
+        // the buffer of unique vertices and the position of each vertex within it
+        let mut vertices = Vec::<Point>::new();
+        let mut positions = HashMap::<Point, usize>::new();
+        // maps each vertex of the sequence to its position in the buffer
+        let indices = self.sequence[..(self.sequence.len() - 1)]
+            .iter()
+            .map(|&vertex| {
+                *positions.entry(vertex).or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() - 1
+                })
+            })
+            .collect::<Vec<usize>>();
+        // the triangles reference the buffer of unique vertices
+        let triangles = self
+            .ears()
+            .into_iter()
+            .map(|triangle| triangle.map(|index| indices[index]))
+            .collect();
+
+        (vertices, triangles)
+    }
+
+    /// Computes the total length of the sides shared with `other`, regardless of their orientation.
+    fn shared_length_with(&self, other: &Self) -> f64 {
+        self.sequence
+            .windows(2)
+            .filter(|side| {
+                other.sequence.windows(2).any(|next| {
+                    (side[0], side[1]) == (next[0], next[1])
+                        || (side[0], side[1]) == (next[1], next[0])
+                })
+            })
+            .map(|side| Vector::between(&(side[0], side[1])).norm())
+            .sum()
+    }
+
+    /// Merges the polygon with the adjacent and coplanar `other` by removing their shared sides and walking along the
+    /// remaining ones.
+    ///
+    /// Nothing is returned when the polygons share no side or when the remaining sides do not describe a single
+    /// simple boundary, for example when the union would have holes.
+    pub fn merge_with(&self, other: &Self) -> Option<Polygon> {
+        // the sides of `other` must be walked in the same rotational sense of the polygon's sides
+        let flipped = self.normal().dot(&other.normal()) < 0f64;
+        // oriented sides of both polygons
+        let mut sides = self
+            .sequence
+            .windows(2)
+            .map(|side| (side[0], side[1]))
+            .chain(other.sequence.windows(2).map(|side| {
+                if flipped {
+                    (side[1], side[0])
+                } else {
+                    (side[0], side[1])
+                }
+            }))
+            .collect::<Vec<Segment>>();
+        let total = sides.len();
+        // shared sides are walked in opposite senses thus they cancel out
+        let shared = sides.iter().copied().collect::<HashSet<Segment>>();
+        sides.retain(|&(u, v)| !shared.contains(&(v, u)));
+        // adjacency is required
+        if sides.len() == total || sides.is_empty() {
+            return None;
+        }
+        // the successor of each vertex along the boundary, which must be unique
+        let mut successors = HashMap::<Point, Point>::new();
+
+        for &(u, v) in &sides {
+            if successors.insert(u, v).is_some() {
+                return None;
+            }
+        }
+        // walks along the boundary starting from any vertex
+        let root = sides[0].0;
+        let mut vertices = vec![root];
+        let mut current = successors[&root];
+
+        while current != root && vertices.len() <= sides.len() {
+            vertices.push(current);
+            current = *successors.get(&current)?;
+        }
+        // the boundary must be a single loop covering all remaining sides
+        if vertices.len() != sides.len() {
+            return None;
+        }
+
+        Polygon::from_points_with_validation(vertices)
+    }
+
+    /// Recovers the sides of the polygon as consecutive segments where the last one closes the loop back to the
+    /// first vertex.
+    pub fn to_segments(&self) -> Vec<Segment> {
+        self.edges().collect()
+    }
+
+    /// Constructs an iterator to visit the sides of the polygon as segments, including the closing one.
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.sequence.windows(2).map(|side| (side[0], side[1]))
+    }
+
+    /// Flattens the polygon on the xy plane as the sequence of coordinates of its vertices, without repeating the
+    /// closing one.
+    ///
+    /// The area enclosed by the resulting footprint equals [Polygon::area_projected].
+    pub fn project_to_xy(&self) -> Vec<(f64, f64)> {
+        self.sequence[1..]
+            .iter()
+            .map(|vertex| (vertex.x, vertex.y))
+            .collect()
+    }
+
+    /// Splits the polygon with the plane passing through `plane_point` with direction `plane_normal` into the parts
+    /// lying above it, namely on the side `plane_normal` points to, and those lying below it.
+    ///
+    /// Concave polygons might be split in more parts on each side. Vertices lying exactly on the plane are always
+    /// considered above it, so that a polygon lying entirely on one side yields no parts on the other side.
+    pub fn split(self, plane_normal: Vector, plane_point: Point) -> (Vec<Polygon>, Vec<Polygon>) {
+        // signed distance of a vertex from the plane
+        let distance = |vertex: &Point| Vector::between(&(plane_point, *vertex)).dot(&plane_normal);
+        // the number of vertices excluding the closing one
+        let n = self.sequence.len() - 1;
+        // the boundary augmented with the points where it crosses the plane, which are marked with their identifier
+        let mut boundary = Vec::<(Point, Option<usize>)>::with_capacity(n + 2);
+        // whether the boundary enters the upper side at each crossing point
+        let mut entering = Vec::<bool>::new();
+
+        for side in self.sequence.windows(2) {
+            let (alpha, beta) = (distance(&side[0]), distance(&side[1]));
+            boundary.push((side[0], None));
+            // the side crosses the plane
+            if (alpha >= 0f64) != (beta >= 0f64) {
+                boundary.push((
+                    side[0].lerp(&side[1], alpha / (alpha - beta)),
+                    Some(entering.len()),
+                ));
+                entering.push(beta >= 0f64);
+            }
+        }
+        // without crossings the polygon lies entirely on one side
+        if entering.is_empty() {
+            return if distance(&self.sequence[0]) >= 0f64 {
+                (vec![self], Vec::new())
+            } else {
+                (Vec::new(), vec![self])
+            };
+        }
+        // the crossing points must come in pairs, otherwise the polygon is split as if it were convex
+        if entering.len() % 2 == 1 {
+            let (above, below) = self.split_by_plane(
+                &plane_normal,
+                plane_normal.dot(&Vector::from(&plane_point)),
+                0f64,
+            );
+            return (above.into_iter().collect(), below.into_iter().collect());
+        }
+        // the position of each crossing point along the boundary
+        let positions = boundary
+            .iter()
+            .enumerate()
+            .filter_map(|(position, (_, crossing))| crossing.map(|_| position))
+            .collect::<Vec<usize>>();
+        // the crossing points lie on the line where the two planes meet, along which they are sorted
+        let direction = plane_normal.cross(&self.normal());
+        let mut order = (0..entering.len()).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| {
+            let (alpha, beta) = (
+                Vector::from(&boundary[positions[a]].0).dot(&direction),
+                Vector::from(&boundary[positions[b]].0).dot(&direction),
+            );
+            alpha.total_cmp(&beta)
+        });
+        // consecutive crossing points along the line delimit the cuts lying inside the polygon
+        let mut partners = vec![0usize; entering.len()];
+
+        for pair in order.chunks(2) {
+            partners[pair[0]] = pair[1];
+            partners[pair[1]] = pair[0];
+        }
+        // constructs the parts lying on one side by walking along the boundary and jumping along the cuts
+        let parts = |above: bool| {
+            let mut visited = vec![false; entering.len()];
+            let mut parts = Vec::<Polygon>::new();
+
+            for root in 0..entering.len() {
+                // each part starts where the boundary enters the side
+                if entering[root] != above || visited[root] {
+                    continue;
+                }
+                let mut vertices = Vec::<Point>::new();
+                let mut crossing = root;
+
+                loop {
+                    visited[crossing] = true;
+                    // walks along the boundary until it leaves the side
+                    let mut position = positions[crossing];
+                    vertices.push(boundary[position].0);
+                    position = (position + 1) % boundary.len();
+
+                    while boundary[position].1.is_none() {
+                        vertices.push(boundary[position].0);
+                        position = (position + 1) % boundary.len();
+                    }
+                    // then jumps along the cut to the next entering point
+                    let leaving = boundary[position].1.unwrap_or_default();
+                    vertices.push(boundary[position].0);
+                    crossing = partners[leaving];
+                    // the part is closed when getting back to its root
+                    if crossing == root || visited[crossing] || entering[crossing] != above {
+                        break;
+                    }
+                }
+
+                parts.extend(Polygon::from_points_with_validation(vertices));
+            }
+
+            parts
+        };
+
+        (parts(true), parts(false))
     }
 
     /// Constructs an iterator to visit the vertices where the last equals the first.
-    pub fn iter(&self) -> PolygonIterator {
+    pub fn iter(&self) -> PolygonIterator<'_> {
         PolygonIterator {
             polygon: self,
             index: 0usize,
@@ -154,6 +1049,39 @@ impl PartialEq for Polygon {
 
 impl Eq for Polygon {}
 
+impl Ord for Polygon {
+    /// Orders the polygons lexicographically by their sorted vertices, consistently with their equality.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.set.cmp(&other.set)
+    }
+}
+
+impl PartialOrd for Polygon {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl TryFrom<Vec<Point>> for Polygon {
+    type Error = PolygonError;
+
+    /// Constructs the polygon once validated, see [Polygon::from_points_with_validation].
+    ///
+    /// Note that the inherent [Polygon::from] skips the validation altogether.
+    fn try_from(vertices: Vec<Point>) -> Result<Self, Self::Error> {
+        Polygon::from_points_with_validation(vertices).ok_or(PolygonError::Degenerate)
+    }
+}
+
+impl TryFrom<&[Point]> for Polygon {
+    type Error = PolygonError;
+
+    /// Like the conversion from a vector, see [`TryFrom<Vec<Point>>`](Polygon::try_from).
+    fn try_from(vertices: &[Point]) -> Result<Self, Self::Error> {
+        Polygon::try_from(vertices.to_vec())
+    }
+}
+
 impl std::hash::Hash for Polygon {
     /// Computes the hash of the polygon as the hash of its vertices.
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -161,6 +1089,66 @@ impl std::hash::Hash for Polygon {
     }
 }
 
+impl std::fmt::Display for Polygon {
+    /// Formats the polygon as the list of its vertices, without repeating the closing one, followed by its area.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+
+        for (index, vertex) in self.sequence[..(self.sequence.len() - 1)]
+            .iter()
+            .enumerate()
+        {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{vertex}")?;
+        }
+
+        write!(f, "] area={:?}", self.area())
+    }
+}
+
+impl std::fmt::Debug for Polygon {
+    /// Formats the polygon as `Polygon { vertices }` where the ordered vertices do not repeat the closing one.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polygon")
+            .field("vertices", &&self.sequence[..(self.sequence.len() - 1)])
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Polygon {
+    /// Serializes the polygon as `{ vertices: [...] }` where the closing vertex is not repeated.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Polygon", 1)?;
+        state.serialize_field("vertices", &self.sequence[..(self.sequence.len() - 1)])?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Polygon {
+    /// Deserializes the polygon from `{ vertices: [...] }` where the closing vertex is not repeated.
+    ///
+    /// The vertices are validated as by [Polygon::from_points_with_validation], degenerate polygons are rejected.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Serialized representation of the polygon.
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Polygon")]
+        struct Representation {
+            vertices: Vec<Point>,
+        }
+
+        let Representation { vertices } = Representation::deserialize(deserializer)?;
+        Polygon::from_points_with_validation(vertices)
+            .ok_or_else(|| serde::de::Error::custom(PolygonError::Degenerate))
+    }
+}
+
 /// The polygon iterator iterates through its vertices.
 pub struct PolygonIterator<'a> {
     /// Reference to the original polygon.
@@ -182,25 +1170,252 @@ impl Iterator for PolygonIterator<'_> {
     }
 }
 
+impl<'a> IntoIterator for &'a Polygon {
+    type Item = Point;
+    type IntoIter = PolygonIterator<'a>;
+
+    /// Visits the vertices where the last equals the first, see [Polygon::iter].
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Polygon {
+    type Item = Point;
+    type IntoIter = std::vec::IntoIter<Point>;
+
+    /// Consumes the polygon to yield its vertices where the last equals the first.
+    fn into_iter(self) -> Self::IntoIter {
+        self.sequence.into_iter()
+    }
+}
+
+/// Constructs a polygon directly from `segments` that are known to form a single closed chain, regardless of their
+/// order and orientation, without the overhead of the graph construction and traversal.
+///
+/// Nothing is returned when the segments describe an open chain, more disconnected chains, branching points or a
+/// degenerate polygon.
+pub fn from_segments_direct(segments: &[Segment]) -> Option<Polygon> {
+    // the points connected to each point
+    let mut adjacencies = HashMap::<Point, Vec<Point>>::new();
+
+    for &(u, v) in segments {
+        adjacencies.entry(u).or_default().push(v);
+        adjacencies.entry(v).or_default().push(u);
+    }
+    // a single closed chain requires each point to have exactly two neighbors
+    if adjacencies.values().any(|neighbors| neighbors.len() != 2) {
+        return None;
+    }
+    // walks along the chain from its first point until it gets back to it
+    let root = segments.first()?.0;
+    let mut vertices = vec![root];
+    let (mut previous, mut current) = (root, adjacencies[&root][0]);
+
+    while current != root {
+        vertices.push(current);
+        // the next point is the neighbor we are not coming from
+        let next = adjacencies[&current]
+            .iter()
+            .copied()
+            .find(|&neighbor| neighbor != previous)?;
+        (previous, current) = (current, next);
+    }
+    // the chain must have visited every point, otherwise the segments are disconnected
+    if vertices.len() != adjacencies.len() {
+        return None;
+    }
+
+    Polygon::from_points_with_validation(vertices)
+}
+
+/// Constructs the levels of detail of `polygons` where each level keeps the polygons whose [Polygon::area] is at least
+/// the corresponding value in `area_thresholds`, which is expected to be sorted in ascending order.
+///
+/// The first level is the most detailed one. The polygons are shared across the levels through reference-counted
+/// pointers so that they are never cloned.
+pub fn lod_hierarchy(polygons: Vec<Polygon>, area_thresholds: &[f64]) -> Vec<Vec<Arc<Polygon>>> {
+    // computes the area of each polygon once
+    let polygons = polygons
+        .into_iter()
+        .map(|polygon| (polygon.area(), Arc::new(polygon)))
+        .collect::<Vec<_>>();
+    // each level shares the polygons above its threshold
+    area_thresholds
+        .iter()
+        .map(|&threshold| {
+            polygons
+                .iter()
+                .filter(|(area, _)| *area >= threshold)
+                .map(|(_, polygon)| Arc::clone(polygon))
+                .collect()
+        })
+        .collect()
+}
+
+/// Merges a `group` of coplanar polygons into a single polygon by greedily merging, through [Polygon::merge_with],
+/// the pair sharing the longest sides at each step.
+///
+/// Nothing is returned when the group is empty or when it cannot be fully merged, for example when it is not
+/// connected.
+pub fn merge_adjacent_coplanar_group(mut group: Vec<Polygon>) -> Option<Polygon> {
+    while group.len() > 1 {
+        // finds the pair of polygons sharing the longest sides
+        let (i, j, _) = (0..group.len())
+            .flat_map(|i| ((i + 1)..group.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, group[i].shared_length_with(&group[j])))
+            .filter(|&(_, _, length)| length > 0f64)
+            .max_by(|(_, _, alpha), (_, _, beta)| alpha.total_cmp(beta))?;
+        // replaces the pair with their union
+        let merged = group[i].merge_with(&group[j])?;
+        group.swap_remove(j);
+        group.swap_remove(i);
+        group.push(merged);
+    }
+
+    group.pop()
+}
+
+/// Merges the `polygons` describing the same surface, namely those whose normals agree within `angle_tolerance` radians,
+/// regardless of their orientation, and which are connected by shared sides, see [merge_adjacent_coplanar_group].
+///
+/// The polygons of a group which cannot be merged into a single simple boundary, for example because their union
+/// would have holes, are yielded unchanged. The groups are yielded by the order of their first polygon.
+pub fn merge_coplanar_adjacent_polygons(
+    polygons: &[Polygon],
+    angle_tolerance: f64,
+) -> Vec<Polygon> {
+    // finds the representative of the group of a polygon while compressing the path
+    fn find(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+
+    // two polygons agree when their normals are parallel within the tolerance
+    let agree = |alpha: &Polygon, beta: &Polygon| {
+        let angle = alpha.normal().angle_with(&beta.normal());
+        angle <= angle_tolerance || std::f64::consts::PI - angle <= angle_tolerance
+    };
+    // joins the groups of adjacent polygons which agree
+    let mut parents = (0..polygons.len()).collect::<Vec<usize>>();
+
+    for i in 0..polygons.len() {
+        for j in (i + 1)..polygons.len() {
+            if polygons[i].shares_sides_with(&polygons[j]) && agree(&polygons[i], &polygons[j]) {
+                let (u, v) = (find(&mut parents, i), find(&mut parents, j));
+                parents[u.max(v)] = u.min(v);
+            }
+        }
+    }
+    // collects the groups by the order of their first polygon
+    let mut groups = Vec::<Vec<Polygon>>::new();
+    let mut positions = HashMap::<usize, usize>::new();
+
+    for (index, polygon) in polygons.iter().enumerate() {
+        let root = find(&mut parents, index);
+        let position = *positions.entry(root).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[position].push(polygon.clone());
+    }
+    // merges each group or keeps its polygons when impossible
+    groups
+        .into_iter()
+        .flat_map(|group| match group.len() {
+            1 => group,
+            _ => merge_adjacent_coplanar_group(group.clone()).map_or(group, |merged| vec![merged]),
+        })
+        .collect()
+}
+
+/// The indices of the polygons sharing at least one side with each polygon, see [compute_adjacency].
+pub type AdjacencyMap = HashMap<usize, Vec<usize>>;
+
+/// Computes the topology of `polygons` by finding, for each polygon index, the sorted indices of the other polygons
+/// sharing at least one side with it, regardless of its orientation.
+///
+/// Rather than comparing every pair of polygons, the sides are indexed by their canonical form such that the
+/// complexity is linear in the total number of sides. Every polygon has an entry, possibly empty.
+pub fn compute_adjacency(polygons: &[Polygon]) -> AdjacencyMap {
+    // the polygons owning each side
+    let mut owners = HashMap::<Segment, Vec<usize>>::new();
+
+    for (index, polygon) in polygons.iter().enumerate() {
+        for side in polygon.edges() {
+            owners
+                .entry(super::point::segment_canonical(side))
+                .or_default()
+                .push(index);
+        }
+    }
+    // links together the polygons owning the same side
+    let mut adjacency = (0..polygons.len())
+        .map(|index| (index, Vec::new()))
+        .collect::<AdjacencyMap>();
+
+    for indices in owners.values() {
+        for &i in indices {
+            adjacency
+                .get_mut(&i)
+                .unwrap()
+                .extend(indices.iter().filter(|&&j| j != i));
+        }
+    }
+    // a pair of polygons might share several sides
+    for neighbors in adjacency.values_mut() {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    adjacency
+}
+
 /// Filters the set `polygons` by discarding those that contain other smaller polygons and share sides with them.
 /// Also, the procedure discards those polygons whose [Polygon::area_projected] is less than `minimum_area_projected`.
 ///
 /// Note that this is a greedy selection procedure that first discard polygons with very small projected area, then it
 /// sorts the left ones by the "real" area, and finally, it iteratively picks those that do not contain the previously
 /// selected polygons.
+///
+/// The selected polygons are yielded in a deterministic order, namely sorted by their vertices, see [Polygon::cmp].
 pub fn filter(
     polygons: Vec<Polygon>,
     minimum_area_projected: f64,
 ) -> impl Iterator<Item = Polygon> {
+    filter_with(polygons, minimum_area_projected, |_| true)
+}
+
+/// Like [filter] but also discards the polygons not satisfying `predicate`, which is applied after checking the
+/// projected area and before selecting the polygons by containment.
+///
+/// Hence, the discarded polygons never prevent larger polygons containing them from being selected.
+pub fn filter_with<P>(
+    polygons: Vec<Polygon>,
+    minimum_area_projected: f64,
+    predicate: P,
+) -> impl Iterator<Item = Polygon>
+where
+    P: Fn(&Polygon) -> bool,
+{
     // discards the polygons whose projected area on the xy plane is less than `minimum_area_projected`
     let mut polygons = polygons
         .into_iter()
         .filter(|polygon| polygon.area_projected() >= minimum_area_projected)
+        .filter(|polygon| predicate(polygon))
         .collect::<Vec<Polygon>>();
     // the mask contains the indices of the polygons that will be taken eventually
     let mut mask = HashSet::<usize>::new();
-    // sorts the polygons by their area
-    polygons.sort_by(|a, b| a.area().partial_cmp(&b.area()).unwrap());
+    // sorts the polygons by their area, breaking ties by their vertices such that the selection is deterministic
+    polygons.sort_by(|a, b| {
+        a.area()
+            .partial_cmp(&b.area())
+            .unwrap()
+            .then_with(|| a.cmp(b))
+    });
     // iteratively picks the valid polygons
     'selection: for (i, polygon) in polygons.iter().enumerate() {
         // checks whether `polygon` contains any of the previously selected polygons
@@ -213,10 +1428,100 @@ pub fn filter(
         // when valid it saves the index in the selection mask
         mask.insert(i);
     }
-    // applies the selection mask and yields the valid polygons
-    polygons
+    // applies the selection mask and yields the valid polygons in a deterministic order
+    let mut polygons = polygons
         .into_iter()
         .enumerate()
-        .filter(move |(index, _)| mask.contains(index))
+        .filter(|(index, _)| mask.contains(index))
         .map(|(_, polygon)| polygon)
+        .collect::<Vec<Polygon>>();
+    polygons.sort();
+
+    polygons.into_iter()
+}
+
+/// Rounds every vertex coordinate of `polygons` to the nearest multiple of `grid_size`.
+///
+/// Vertices collapsing onto the same grid point are merged and the polygons becoming degenerate are discarded,
+/// see [Polygon::from_points_with_validation].
+///
+/// # Panics
+///
+/// Panics when `grid_size` is not finite and positive, which would otherwise silently discard every polygon.
+pub fn snap_to_grid(polygons: Vec<Polygon>, grid_size: f64) -> Vec<Polygon> {
+    assert!(
+        grid_size.is_finite() && grid_size > 0f64,
+        "The grid size must be finite and positive, {} given.",
+        grid_size
+    );
+    // rounds a single coordinate to the grid
+    let snap = |value: f64| (value / grid_size).round() * grid_size;
+
+    polygons
+        .into_iter()
+        .filter_map(|polygon| {
+            // snaps each vertex but the closing one, which repeats the first
+            let mut vertices = polygon.sequence;
+            vertices.pop();
+
+            Polygon::from_points_with_validation(
+                vertices
+                    .into_iter()
+                    .map(|Point { x, y, z }| Point {
+                        x: snap(x),
+                        y: snap(y),
+                        z: snap(z),
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Clips `polygon` to the axis-aligned box described by its `min` and `max` corners on the xy plane using the
+/// Sutherland-Hodgman algorithm.
+///
+/// The clipped polygon lies on the plane of `polygon` because the elevation of the new vertices is interpolated
+/// along the clipped sides. Nothing is returned when the intersection is empty or degenerate.
+pub fn intersect_with_aabb(polygon: &Polygon, min: &Point, max: &Point) -> Option<Polygon> {
+    // fast path when the bounding boxes do not overlap
+    if polygon.boundary.1.x < min.x
+        || polygon.boundary.0.x > max.x
+        || polygon.boundary.1.y < min.y
+        || polygon.boundary.0.y > max.y
+    {
+        return None;
+    }
+    // counterclockwise corners of the box such that its inside lies on the left of each side
+    let corners = [
+        Point {
+            x: min.x,
+            y: min.y,
+            z: 0f64,
+        },
+        Point {
+            x: max.x,
+            y: min.y,
+            z: 0f64,
+        },
+        Point {
+            x: max.x,
+            y: max.y,
+            z: 0f64,
+        },
+        Point {
+            x: min.x,
+            y: max.y,
+            z: 0f64,
+        },
+    ];
+    // clips the polygon, excluding its closing vertex, against each side of the box
+    let vertices = (0..corners.len()).fold(polygon.sequence[1..].to_vec(), |vertices, index| {
+        super::plane::clip(
+            &vertices,
+            &(corners[index], corners[(index + 1) % corners.len()]),
+        )
+    });
+    // discards empty or degenerate intersections
+    Polygon::from_points_with_validation(vertices)
 }
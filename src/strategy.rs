@@ -0,0 +1,130 @@
+use super::{graph::SegmentGraph, point::Segment};
+
+use hashbrown::HashMap;
+
+/// Strategy algorithm to elect optimal segment as successor when recursively traversing the graph.
+pub trait ElectionStrategy {
+    /// Elects optimal segment as successor of `current`, reached from `previous`, when recursively traversing
+    /// `graph`.
+    fn elect(
+        &mut self,
+        graph: &SegmentGraph,
+        previous: Segment,
+        current: Segment,
+    ) -> Option<Segment>;
+
+    /// Forgets any state depending on the traversed graph, which is called before traversing a new one.
+    fn reset(&mut self) {}
+}
+
+/// This election strategy runs in `O(m)` where `m` is the number of adjacencies of the each segment
+/// using the policy function and the referenced graph.
+pub struct GreedyElectionStrategy<T>
+where
+    T: PartialOrd,
+{
+    cache: HashMap<(Segment, Segment), Option<Segment>>,
+    policy: fn(&SegmentGraph, Segment, Segment, Segment) -> T,
+}
+
+impl<T> GreedyElectionStrategy<T>
+where
+    T: PartialOrd,
+{
+    /// Constructs a greedy election strategy which elects the successor `next` minimizing the outcome of
+    /// `policy(graph, previous, current, next)`.
+    pub fn from(policy: fn(&SegmentGraph, Segment, Segment, Segment) -> T) -> Self {
+        Self {
+            cache: HashMap::new(),
+            policy,
+        }
+    }
+}
+
+impl<T> ElectionStrategy for GreedyElectionStrategy<T>
+where
+    T: PartialOrd,
+{
+    /// Elects optimal segment as successor when recursively traversing the graph using the policy given to
+    /// [GreedyElectionStrategy::from].
+    fn elect(
+        &mut self,
+        graph: &SegmentGraph,
+        previous: Segment,
+        current: Segment,
+    ) -> Option<Segment> {
+        // gets the optiomal successor if cached otherwise computes it with the policy function
+        *self.cache.entry((previous, current)).or_insert_with(|| {
+            // leverages the ordering of the policy result to choose the best
+            graph
+                .successors(&current)
+                .map(|&segment| (segment, (self.policy)(graph, previous, current, segment)))
+                .min_by(|(_, alpha), (_, beta)| alpha.partial_cmp(beta).unwrap())
+                .map(|(successor, _)| successor)
+        })
+    }
+
+    /// Clears the cache of the elected successors.
+    fn reset(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// This election strategy elects the successor minimizing the clockwise angle projected on the xy plane, without
+/// considering the coplanarity of the segments nor their weights.
+///
+/// It is meant for flat plans, where all points share the same z coordinate, as the coplanarity is then irrelevant
+/// and costly to compute, which matches the classic reconstruction of polygons in two dimensions.
+#[derive(Default)]
+pub struct MinimumAngleStrategy {
+    cache: HashMap<Segment, Option<Segment>>,
+}
+
+impl ElectionStrategy for MinimumAngleStrategy {
+    /// Elects the successor with the smallest clockwise angle from `current`, which does not depend on `previous`.
+    fn elect(&mut self, graph: &SegmentGraph, _: Segment, current: Segment) -> Option<Segment> {
+        *self.cache.entry(current).or_insert_with(|| {
+            graph
+                .successors(&current)
+                .map(|&segment| (segment, super::plane::theta(&current, &segment)))
+                .min_by(|(_, alpha), (_, beta)| alpha.total_cmp(beta))
+                .map(|(successor, _)| successor)
+        })
+    }
+
+    /// Clears the cache of the elected successors.
+    fn reset(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// Constructs the default strategies based on clockwise angle between segments and coplanarity to extract polygons.
+///
+/// Two different criteria are employed to chose on which segment to recur when following a path. First, we pick
+/// the next segment minimizing the pair `(theta, coplanarity)` where `theta` is the clockwise angle between the
+/// current segment and the next candidate projected on the xy plane whereas coplanarity is the area of the tetrahedron
+/// considering the four points belonging to the previous segment, the current one and the next candidate. Second, we
+/// repeat the recursive traversal by constructing other polygons using as criterion the minimization of the opposite
+/// pair, that is `(coplanarity, theta)`. This helps identifies polygons that vertically overlap but are distinct.
+///
+/// The leading criterion of each pair is divided by the weight of the candidate, so that more reliable segments are
+/// preferred. Unweighted segments have unit weight and are not affected.
+pub fn defaults() -> [GreedyElectionStrategy<(f64, f64)>; 2] {
+    [
+        // first strategy to elect successor segment prioritizes the clockwise angle projected on the xy plane
+        GreedyElectionStrategy::from(|graph, previous, current, next| {
+            (
+                super::plane::theta(&current, &next) / graph.weight(&next) as f64,
+                super::plane::coplanarity(previous.0, current.0, current.1, next.1),
+            )
+        }),
+        // second strategy to elect successor segment prioritizes the coplanarity
+        GreedyElectionStrategy::from(|graph, previous, current, next| {
+            (
+                super::plane::coplanarity(previous.0, current.0, current.1, next.1)
+                    / graph.weight(&next) as f64,
+                super::plane::theta(&current, &next),
+            )
+        }),
+    ]
+}
@@ -2,9 +2,11 @@ use super::{
     graph::SegmentGraph,
     point::{Point, Segment},
     polygon::Polygon,
+    strategy::{self, ElectionStrategy},
 };
 
 use hashbrown::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// The result of the recursive graph traversal when constructing its faces, namely polygons.
 enum Status {
@@ -16,53 +18,18 @@ enum Status {
     PathClosing,
 }
 
-/// Strategy algorithm to elect optimal segment as successor when recursively traversing the graph.
-trait ElectionStrategy {
-    /// Elects optimal segment as successor when recursively traversing the graph.
-    fn elect(&mut self, previous: Segment, current: Segment) -> Option<Segment>;
-}
-
-/// This election strategy runs in `O(m)` where `m` is the number of adjacencies of the each segment
-/// using the policy function and the referenced graph.
-struct GreedyElectionStrategy<'a, T>
-where
-    T: PartialOrd,
-{
-    cache: HashMap<(Segment, Segment), Option<Segment>>,
-    graph: &'a SegmentGraph,
-    policy: fn(Segment, Segment, Segment) -> T,
-}
-
-impl<'a, T> GreedyElectionStrategy<'a, T>
-where
-    T: PartialOrd,
-{
-    /// Constructs a greedy election strategy using a specific policy and referencing the given graph.
-    fn from(graph: &'a SegmentGraph, policy: fn(Segment, Segment, Segment) -> T) -> Self {
-        Self {
-            cache: HashMap::new(),
-            graph,
-            policy,
-        }
-    }
-}
-
-impl<T> ElectionStrategy for GreedyElectionStrategy<'_, T>
-where
-    T: PartialOrd,
-{
-    /// Elects optimal segment as successor when recursively traversing the graph using the policy [CachingGreedyElectionStrategy::policy].
-    fn elect(&mut self, previous: Segment, current: Segment) -> Option<Segment> {
-        // gets the optiomal successor if cached otherwise computes it with the policy function
-        *self.cache.entry((previous, current)).or_insert_with(|| {
-            // leverages the ordering of the policy result to choose the best
-            self.graph.adjacencies[&current]
-                .iter()
-                .map(|&segment| (segment, (self.policy)(previous, current, segment)))
-                .min_by(|(_, alpha), (_, beta)| alpha.partial_cmp(beta).unwrap())
-                .map(|(successor, _)| successor)
-        })
-    }
+/// Some metrics about the exploration of a graph during its traversal, which help understanding why a polygon is
+/// missing or why the traversal is slow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraversalStats {
+    /// The number of segments which have been exhaustively explored.
+    pub paths_explored: usize,
+    /// The number of times the traversal went back without exploring further nor closing a path.
+    pub backtracks: usize,
+    /// The number of closed paths, including the duplicated polygons.
+    pub polygons_found: usize,
+    /// The maximum number of segments held at once on the recursion stack.
+    pub max_depth_reached: usize,
 }
 
 /// A traversal instance recursively visits a graph and extracts its polygons according to specific policies.
@@ -71,6 +38,12 @@ struct Traversal<'a> {
     stack: Vec<Segment>,
     depth: HashMap<Segment, usize>,
     paths: HashSet<Polygon>,
+    /// The optional flag that stops the traversal early when raised.
+    cancel: Option<&'a AtomicBool>,
+    /// The maximum number of segments on the recursion stack, beyond which the traversal backtracks.
+    max_depth: usize,
+    /// The optional metrics which are only collected when requested.
+    stats: Option<TraversalStats>,
 }
 
 impl<'a> Traversal<'a> {
@@ -81,9 +54,39 @@ impl<'a> Traversal<'a> {
             stack: Vec::new(),
             depth: HashMap::new(),
             paths: HashSet::new(),
+            cancel: None,
+            max_depth: usize::MAX,
+            stats: None,
         }
     }
 
+    /// Backtracks without closing the path as soon as it holds `max_depth` segments, such that the recursion never
+    /// gets deeper than `max_depth` levels.
+    fn limited(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Stops the traversal before visiting the next source segment as soon as `cancel` is raised.
+    fn cancellable(mut self, cancel: Option<&'a AtomicBool>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Like [Traversal::run] but also collects metrics about the exploration of the graph.
+    pub fn run_with_stats(
+        mut self,
+        strategies: &mut [impl ElectionStrategy],
+    ) -> (Vec<Polygon>, TraversalStats) {
+        self.stats = Some(TraversalStats::default());
+        self.explore(strategies);
+
+        (
+            self.paths.into_iter().collect(),
+            self.stats.unwrap_or_default(),
+        )
+    }
+
     /// Constructs a set of unique polygons from the graph by performing a policy-guided graph traversal.
     ///
     /// The inexact procedure is pretty efficient because it does not instantiate a branching recursion tree.
@@ -91,35 +94,54 @@ impl<'a> Traversal<'a> {
     /// segments and `k` is the average polygon's size. This ensures that the complexity is always polynomial
     /// and NEVER degenerates to exponential by design.
     pub fn run(mut self, strategies: &mut [impl ElectionStrategy]) -> Vec<Polygon> {
+        self.explore(strategies);
+        // yields found polygons
+        self.paths.into_iter().collect()
+    }
+
+    /// Traverses the whole graph using all `strategies` and collects the closed paths.
+    fn explore(&mut self, strategies: &mut [impl ElectionStrategy]) {
+        // the strategies might have been used on another graph
+        strategies.iter_mut().for_each(ElectionStrategy::reset);
         // traverses the whole graph using all strategies
-        self.graph
-            .adjacencies
-            .iter()
-            .for_each(|(source, successors)| {
-                // the source is put at the base of the recursion stack
-                self.depth.insert(*source, 0);
-                self.stack.push(*source);
-                // naively tries every successor to have a `previous` segment in further recursive calls
-                successors.iter().for_each(|successor| {
-                    // applies every traversal strategy
-                    strategies.iter_mut().for_each(|strategy| {
-                        // recursive traversal from `successor` on
-                        self.traverse(successor, source, strategy).ok();
-                        // at debug time verifies that the source is still at the root of the recursion stack
-                        debug_assert_eq!(self.stack.len(), 1);
-                        debug_assert_eq!(self.depth.len(), 1);
-                    });
+        for (source, successors) in &self.graph.adjacencies {
+            // stops early when cancelled
+            if self
+                .cancel
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            {
+                break;
+            }
+            // the source is put at the base of the recursion stack
+            self.depth.insert(*source, 0);
+            self.stack.push(*source);
+            // naively tries every successor to have a `previous` segment in further recursive calls
+            successors.iter().for_each(|successor| {
+                // applies every traversal strategy
+                strategies.iter_mut().for_each(|strategy| {
+                    // recursive traversal from `successor` on
+                    self.traverse(successor, source, strategy).ok();
+                    // at debug time verifies that the source is still at the root of the recursion stack
+                    debug_assert_eq!(self.stack.len(), 1);
+                    debug_assert_eq!(self.depth.len(), 1);
                 });
-                // removes the source from the root of the stack
-                if let Some(segment) = self.stack.pop() {
-                    self.depth.remove(&segment);
-                }
-                // ensures that the recursion stack is empty
-                debug_assert_eq!(self.stack.len(), 0);
-                debug_assert_eq!(self.depth.len(), 0);
             });
-        // yields found polygons
-        self.paths.into_iter().collect()
+            // removes the source from the root of the stack
+            if let Some(segment) = self.stack.pop() {
+                self.depth.remove(&segment);
+            }
+            // ensures that the recursion stack is empty
+            debug_assert_eq!(self.stack.len(), 0);
+            debug_assert_eq!(self.depth.len(), 0);
+        }
+    }
+
+    /// Updates the metrics when they are collected.
+    #[inline]
+    fn record(&mut self, update: impl FnOnce(&mut TraversalStats)) {
+        if let Some(stats) = self.stats.as_mut() {
+            update(stats);
+        }
     }
 
     /// Recursive traversal of `current` segment from `previous` where the minimization of `criterion(previous, current, candidate)`
@@ -132,6 +154,7 @@ impl<'a> Traversal<'a> {
     ) -> Result<Status, ()> {
         if self.depth.contains_key(&(current.1, current.0)) {
             // we are traversing an already explored segment by walking on it in the opposite sense thus we must backtrack
+            self.record(|stats| stats.backtracks += 1);
             Ok(Status::Backtracking)
         } else if let Some(&position) = self.depth.get(current) {
             // we are visiting an already visited segment, this means we are closing a path
@@ -142,15 +165,22 @@ impl<'a> Traversal<'a> {
                     .collect::<Vec<Point>>(),
             ));
             // we save the detected polygon and we go back one level
+            self.record(|stats| stats.polygons_found += 1);
             Ok(Status::PathClosing)
+        } else if self.stack.len() >= self.max_depth {
+            // the path is too long to be closed thus we give up on it
+            self.record(|stats| stats.backtracks += 1);
+            Ok(Status::Backtracking)
         } else {
             // otherwise we explore the new segment by pushing it onto the stack
             if let Some(last) = self.stack.last() {
                 self.depth.insert(*current, self.depth[last] + 1);
                 self.stack.push(*current);
             }
+            let depth = self.stack.len();
+            self.record(|stats| stats.max_depth_reached = stats.max_depth_reached.max(depth));
             // chooses the next segment that minimizes the criterion
-            if let Some(successor) = strategy.elect(*previous, *current) {
+            if let Some(successor) = strategy.elect(self.graph, *previous, *current) {
                 // and recursively traverses it
                 self.traverse(&successor, current, strategy).ok();
             }
@@ -159,36 +189,37 @@ impl<'a> Traversal<'a> {
                 self.depth.remove(&segment);
             }
             // `current` has been exhaustively explored and we can go back one level
+            self.record(|stats| stats.paths_explored += 1);
             Ok(Status::Exploring)
         }
     }
 }
 
-/// Applies two distinct policies based on clockwise angle between segments and coplanarity to extract polygons.
+/// Extracts the polygons by traversing the graph with the default strategies, see [strategy::defaults].
 ///
-/// Two different criteria are employed to chose on which segment to recur when following a path. First, we pick
-/// the next segment minimizing the pair `(theta, coplanarity)` where `theta` is the clockwise angle between the
-/// current segment and the next candidate projected on the xy plane whereas coplanarity is the area of the tetrahedron
-/// considering the four points belonging to the previous segment, the current one and the next candidate. Second, we
-/// repeat the recursive traversal by constructing other polygons using as criterion the minimization of the opposite
-/// pair, that is `(coplanarity, theta)`. This helps identifies polygons that vertically overlap but are distinct.
+/// The traversal stops early, yielding only the polygons found so far, as soon as the optional `cancel` is raised.
 #[inline]
-pub(super) fn traverse(graph: &SegmentGraph) -> Vec<Polygon> {
-    // by default we traverse using two strategies to detect polygons
-    Traversal::from(graph).run(&mut [
-        // first strategy to elect successor segment prioritizes the clockwise angle projected on the xy plane
-        GreedyElectionStrategy::from(graph, |previous, current, next| {
-            (
-                super::plane::theta(&current, &next),
-                super::plane::coplanarity(previous.0, current.0, current.1, next.1),
-            )
-        }),
-        // second strategy to elect successor segment prioritizes the coplanarity
-        GreedyElectionStrategy::from(graph, |previous, current, next| {
-            (
-                super::plane::coplanarity(previous.0, current.0, current.1, next.1),
-                super::plane::theta(&current, &next),
-            )
-        }),
-    ])
+pub(super) fn traverse(graph: &SegmentGraph, cancel: Option<&AtomicBool>) -> Vec<Polygon> {
+    traverse_with(graph, &mut strategy::defaults(), usize::MAX, cancel)
+}
+
+/// Like [traverse] but extracts the polygons with the given `strategies`, applied one after the other, while the
+/// paths are abandoned once they hold `max_depth` segments.
+#[inline]
+pub(super) fn traverse_with(
+    graph: &SegmentGraph,
+    strategies: &mut [impl ElectionStrategy],
+    max_depth: usize,
+    cancel: Option<&AtomicBool>,
+) -> Vec<Polygon> {
+    Traversal::from(graph)
+        .cancellable(cancel)
+        .limited(max_depth)
+        .run(strategies)
+}
+
+/// Extracts the polygons by traversing `graph` with the default strategies, see [strategy::defaults], while also
+/// collecting metrics about its exploration, see [TraversalStats].
+pub fn traverse_with_stats(graph: &SegmentGraph) -> (Vec<Polygon>, TraversalStats) {
+    Traversal::from(graph).run_with_stats(&mut strategy::defaults())
 }
@@ -0,0 +1,88 @@
+use super::{point::Point, polygon::Polygon};
+
+/// The reasons why a text cannot be parsed as a polygon in Well-Known Text format.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WktError {
+    /// The text does not start with the `POLYGON Z` tag.
+    UnexpectedTag,
+    /// The rings are not enclosed within double parentheses.
+    UnbalancedParentheses,
+    /// The polygon has inner rings which are not supported.
+    UnsupportedHoles,
+    /// A vertex is not made of three valid coordinates.
+    InvalidCoordinates(String),
+    /// The vertices do not describe a valid polygon.
+    DegeneratePolygon,
+}
+
+impl std::fmt::Display for WktError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WktError::UnexpectedTag => write!(f, "expected a `POLYGON Z` geometry"),
+            WktError::UnbalancedParentheses => {
+                write!(f, "the ring is not enclosed within `((` and `))`")
+            }
+            WktError::UnsupportedHoles => write!(f, "polygons with inner rings are not supported"),
+            WktError::InvalidCoordinates(vertex) => write!(f, "invalid vertex `{}`", vertex),
+            WktError::DegeneratePolygon => write!(f, "the vertices do not describe a polygon"),
+        }
+    }
+}
+
+impl std::error::Error for WktError {}
+
+impl Polygon {
+    /// Serializes the polygon in ISO Well-Known Text format as `POLYGON Z ((x1 y1 z1, ..., x1 y1 z1))` where the
+    /// closing vertex repeats the first one.
+    pub fn to_wkt(&self) -> String {
+        format!(
+            "POLYGON Z (({}))",
+            self.iter()
+                .map(|vertex| format!("{:?} {:?} {:?}", vertex.x, vertex.y, vertex.z))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    /// Parses a polygon in the same format produced by [Polygon::to_wkt], regardless of the letter case and of
+    /// the whitespaces.
+    pub fn from_wkt(s: &str) -> Result<Polygon, WktError> {
+        // the ring starts at the first parenthesis
+        let body = s.trim();
+        let start = body.find('(').ok_or(WktError::UnbalancedParentheses)?;
+        // the tag is made of the geometry type followed by the dimension
+        let tag = body[..start]
+            .split_whitespace()
+            .map(str::to_uppercase)
+            .collect::<Vec<String>>();
+        if tag != ["POLYGON", "Z"] {
+            return Err(WktError::UnexpectedTag);
+        }
+        // a single ring is enclosed within double parentheses
+        let ring = body[start..]
+            .strip_prefix("((")
+            .and_then(|ring| ring.strip_suffix("))"))
+            .ok_or(WktError::UnbalancedParentheses)?;
+        if ring.contains('(') || ring.contains(')') {
+            return Err(WktError::UnsupportedHoles);
+        }
+        // each vertex is made of three coordinates separated by whitespaces
+        let vertices = ring
+            .split(',')
+            .map(|vertex| {
+                let coordinates = vertex
+                    .split_whitespace()
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|_| WktError::InvalidCoordinates(vertex.trim().to_string()))?;
+
+                match coordinates[..] {
+                    [x, y, z] => Ok(Point { x, y, z }),
+                    _ => Err(WktError::InvalidCoordinates(vertex.trim().to_string())),
+                }
+            })
+            .collect::<Result<Vec<Point>, WktError>>()?;
+
+        Polygon::from_points_with_validation(vertices).ok_or(WktError::DegeneratePolygon)
+    }
+}
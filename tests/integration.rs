@@ -98,6 +98,2730 @@ fn church() {
     );
 }
 
+#[test]
+fn signed_distance_to_plane() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 2f64),
+        point!(4f64, 0f64, 2f64),
+        point!(4f64, 2f64, 2f64),
+        point!(0f64, 2f64, 2f64),
+    ]);
+    let up = polygonum::plane::Vector {
+        x: 0f64,
+        y: 0f64,
+        z: 1f64,
+    };
+    assert_eq!(point!(2f64, 1f64, 2f64), polygon.centroid());
+    assert_eq!(2f64, polygon.signed_distance_to_plane(&up, 0f64));
+    assert_eq!(-1f64, polygon.signed_distance_to_plane(&up, 3f64));
+    assert!(polygon.on_positive_side_of_plane(&up, 0f64));
+    assert!(polygon.on_negative_side_of_plane(&up, 3f64));
+    assert!(
+        !polygon.on_positive_side_of_plane(&up, 2f64)
+            && !polygon.on_negative_side_of_plane(&up, 2f64),
+        "A polygon lying on the plane is on neither side."
+    );
+}
+
+#[test]
+fn bsp() {
+    fn count(node: &polygonum::bsp::BspNode) -> usize {
+        node.polygons.len()
+            + node.front.as_ref().map_or(0, |front| count(front))
+            + node.back.as_ref().map_or(0, |back| count(back))
+    }
+
+    let tree = polygonum::bsp::build(vec![
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 0f64),
+            point!(4f64, 0f64, 0f64),
+            point!(4f64, 4f64, 0f64),
+            point!(0f64, 4f64, 0f64),
+        ]),
+        polygonum::Polygon::from(vec![
+            point!(2f64, 0f64, -1f64),
+            point!(2f64, 4f64, -1f64),
+            point!(2f64, 4f64, 1f64),
+            point!(2f64, 0f64, 1f64),
+        ]),
+    ]);
+    assert_eq!(
+        3,
+        count(&tree),
+        "Two crossing polygons are partitioned in three parts."
+    );
+}
+
+#[test]
+fn lerp() {
+    let a = point!(0f64, 0f64, 1f64);
+    let b = point!(4f64, 2f64, 5f64);
+    assert_eq!(point!(1f64, 0.5, 1f64), a.lerp_2d(&b, 0.25));
+    assert_eq!(point!(1f64, 0.5, 2f64), a.lerp_3d(&b, 0.25));
+}
+
+#[test]
+fn snap_to_grid() {
+    let polygons = polygonum::snap_to_grid(
+        vec![
+            polygonum::Polygon::from(vec![
+                point!(0.1, -0.2, 0.9),
+                point!(4.2, 0.1, 1.1),
+                point!(4.1, 0.3, 1f64),
+                point!(3.9, 3.8, 0.8),
+                point!(0.2, 4.1, 1.2),
+            ]),
+            polygonum::Polygon::from(vec![
+                point!(0.1, 0.1, 0f64),
+                point!(0.2, 0.3, 0f64),
+                point!(0.3, 0.1, 0f64),
+            ]),
+        ],
+        1f64,
+    );
+    assert_eq!(
+        1,
+        polygons.len(),
+        "The triangle collapses onto a single grid point."
+    );
+    assert_eq!(
+        vec![
+            point!(0f64, 0f64, 1f64),
+            point!(4f64, 0f64, 1f64),
+            point!(4f64, 4f64, 1f64),
+            point!(0f64, 4f64, 1f64),
+            point!(0f64, 0f64, 1f64),
+        ],
+        polygons[0].iter().collect::<Vec<_>>(),
+        "Vertices collapsing onto the same grid point are merged."
+    );
+}
+
+#[test]
+fn to_segments() {
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+        segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+        segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+        segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+    ];
+    let polygons = polygonum::polygonalize(&segments, false, 0.01);
+    let sides = polygons[0].to_segments();
+    assert_eq!(4, sides.len());
+    assert_eq!(sides[0].0, sides[3].1, "The last side closes the loop.");
+    assert_eq!(
+        1,
+        polygonum::polygonalize(&sides, false, 0.01).len(),
+        "The sides of a polygon are polygonalized back to the same polygon."
+    );
+}
+
+#[test]
+fn to_dot_with_coordinates() {
+    assert_eq!(
+        "graph {\n    0 [label=\"0.0,0.0,0.0\"];\n    1 [label=\"0.0,1.0,0.0\"];\n    2 [label=\"1.0,0.0,0.5\"];\n    0 -- 1;\n    0 -- 2;\n    1 -- 2;\n}\n",
+        polygonum::PointGraph::from(&[
+            segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0.5),
+            segment!(1f64, 0f64, 0.5 => 0f64, 1f64, 0f64),
+            segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        ])
+        .to_dot_with_coordinates(1),
+    );
+}
+
+#[test]
+fn degrees() {
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(0f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+    ];
+    let degrees = polygonum::Pipeline::from(&segments).apply(|graph| {
+        [segments[0], segments[4]]
+            .map(|segment| (graph.in_degree(&segment), graph.out_degree(&segment)))
+            .into_iter()
+    });
+    assert_eq!(vec![(2, 1), (2, 2)], degrees);
+}
+
+#[test]
+fn scale_around_centroid() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(6f64, 0f64, 3f64),
+        point!(6f64, 2f64, 3f64),
+        point!(2f64, 5f64, 1f64),
+    ]);
+    for factor in [0.5, 2f64, 3f64] {
+        let scaled = polygon.scale_around_centroid(factor);
+        assert!(
+            (scaled.area() - polygon.area() * factor * factor).abs() < 1e-9,
+            "Scaling by a factor scales the area by its square."
+        );
+        assert!(
+            (scaled.centroid().x - polygon.centroid().x).abs() < 1e-9
+                && (scaled.centroid().y - polygon.centroid().y).abs() < 1e-9
+                && (scaled.centroid().z - polygon.centroid().z).abs() < 1e-9,
+            "Scaling preserves the centroid."
+        );
+    }
+}
+
+#[test]
+fn intersect_with_aabb() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(4f64, 0f64, 4f64),
+        point!(4f64, 4f64, 4f64),
+        point!(0f64, 4f64, 0f64),
+    ]);
+    let clipped = polygonum::intersect_with_aabb(
+        &polygon,
+        &point!(1f64, 1f64, 0f64),
+        &point!(3f64, 5f64, 0f64),
+    )
+    .unwrap();
+    assert!((clipped.area_projected() - 6f64).abs() < 1e-9);
+    assert!(
+        clipped.iter().all(|point| (point.z - point.x).abs() < 1e-9),
+        "Clipped vertices lie on the original plane."
+    );
+    assert!(polygonum::intersect_with_aabb(
+        &polygon,
+        &point!(5f64, 5f64, 0f64),
+        &point!(6f64, 6f64, 0f64),
+    )
+    .is_none());
+    assert!(
+        polygonum::intersect_with_aabb(
+            &polygon,
+            &point!(4f64, 4f64, 0f64),
+            &point!(6f64, 6f64, 0f64),
+        )
+        .is_none(),
+        "Touching at a single vertex yields no intersection."
+    );
+}
+
+#[test]
+fn volume_below() {
+    // a 4 by 2 rectangle sloping from elevation 0 to elevation 4
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(4f64, 0f64, 4f64),
+        point!(4f64, 2f64, 4f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    assert_eq!(0f64, polygon.volume_below(0f64));
+    assert!((polygon.volume_below(2f64) - 4f64).abs() < 1e-9);
+    assert!((polygon.volume_below(4f64) - 16f64).abs() < 1e-9);
+    assert!((polygon.volume_below(5f64) - 24f64).abs() < 1e-9);
+    assert!(
+        (polygon.flip().volume_below(4f64) - 16f64).abs() < 1e-9,
+        "The volume of a flipped polygon is still positive."
+    );
+}
+
+#[test]
+fn weighted() {
+    let segments = dataset!("house.geojson")
+        .iter()
+        .map(|&segment| (segment, 2f32))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        18,
+        polygonum::polygonalize_weighted(&segments, true, 0.01)
+            .unwrap()
+            .len(),
+        "Uniform weights do not affect the constructed polygons."
+    );
+    let triangle = [
+        (segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64), 0.5),
+        (segment!(1f64, 0f64, 0f64 => 0f64, 1f64, 0f64), 3f32),
+        (segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64), 1f32),
+    ];
+    let weights = polygonum::Pipeline::weighted(&triangle)
+        .unwrap()
+        .apply(|graph| {
+            [
+                graph.weight(&triangle[0].0),
+                graph.weight(&(triangle[1].0 .1, triangle[1].0 .0)),
+                graph.weight(&segment!(5f64, 5f64, 5f64 => 6f64, 6f64, 6f64)),
+            ]
+            .into_iter()
+        });
+    assert_eq!(
+        vec![0.5, 3f32, 1f32],
+        weights,
+        "Weights do not depend on the orientation and default to one."
+    );
+}
+
+#[test]
+fn area_of_intersection_xy() {
+    let square = |x: f64, y: f64, z: f64| {
+        polygonum::Polygon::from(vec![
+            point!(x, y, z),
+            point!(x + 2f64, y, z),
+            point!(x + 2f64, y + 2f64, z + 1f64),
+            point!(x, y + 2f64, z + 1f64),
+        ])
+    };
+    assert!(
+        (square(0f64, 0f64, 0f64).area_of_intersection_xy(&square(1f64, 1f64, 5f64)) - 1f64).abs()
+            < 1e-9
+    );
+    assert!(
+        (square(0f64, 0f64, 0f64).area_of_intersection_xy(&square(0f64, 0f64, 5f64)) - 4f64).abs()
+            < 1e-9
+    );
+    assert_eq!(
+        0f64,
+        square(0f64, 0f64, 0f64).area_of_intersection_xy(&square(3f64, 0f64, 0f64))
+    );
+    assert!(
+        (square(0f64, 0f64, 0f64).area_of_intersection_xy(&square(1f64, 1f64, 5f64).flip()) - 1f64)
+            .abs()
+            < 1e-9,
+        "The orientation of the clipping polygon does not matter."
+    );
+}
+
+#[test]
+fn from_segments_direct() {
+    let polygon = polygonum::from_segments_direct(&[
+        segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+        segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+        segment!(0f64, 0f64, 0f64 => 10f64, 0f64, 5f64),
+        segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+    ]);
+    assert_eq!(
+        5,
+        polygon.unwrap().iter().count(),
+        "Shuffled and flipped segments still form a closed chain."
+    );
+    assert!(
+        polygonum::from_segments_direct(&[
+            segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+            segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+            segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+        ])
+        .is_none(),
+        "An open chain does not form a polygon."
+    );
+    assert!(
+        polygonum::from_segments_direct(&[
+            segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+            segment!(1f64, 0f64, 0f64 => 0f64, 1f64, 0f64),
+            segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+            segment!(5f64, 0f64, 0f64 => 6f64, 0f64, 0f64),
+            segment!(6f64, 0f64, 0f64 => 5f64, 1f64, 0f64),
+            segment!(5f64, 1f64, 0f64 => 5f64, 0f64, 0f64),
+        ])
+        .is_none(),
+        "Disconnected chains do not form a single polygon."
+    );
+}
+
+#[test]
+fn to_indexed_mesh() {
+    // a tilted concave polygon shaped like an L
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(4f64, 0f64, 2f64),
+        point!(4f64, 1f64, 2f64),
+        point!(1f64, 1f64, 0.5),
+        point!(1f64, 3f64, 0.5),
+        point!(0f64, 3f64, 0f64),
+    ]);
+    let (vertices, triangles) = polygon.to_indexed_mesh();
+    assert_eq!(6, vertices.len());
+    assert_eq!(4, triangles.len());
+    // computes the area of each triangle in space
+    let area = triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let (a, b, c) = (vertices[a], vertices[b], vertices[c]);
+            let (u, v) = (
+                (b.x - a.x, b.y - a.y, b.z - a.z),
+                (c.x - a.x, c.y - a.y, c.z - a.z),
+            );
+            let cross = (
+                u.1 * v.2 - u.2 * v.1,
+                u.2 * v.0 - u.0 * v.2,
+                u.0 * v.1 - u.1 * v.0,
+            );
+            (cross.0 * cross.0 + cross.1 * cross.1 + cross.2 * cross.2).sqrt() / 2f64
+        })
+        .sum::<f64>();
+    assert!(
+        (area - polygon.area()).abs() < 1e-9,
+        "The triangles tessellate the polygon exactly."
+    );
+}
+
+#[test]
+fn lod_hierarchy() {
+    let polygons = polygonum::polygonalize(dataset!("house.geojson"), true, 0.01);
+    let total = polygons.len();
+    let levels = polygonum::lod_hierarchy(polygons, &[0f64, 10f64, f64::INFINITY]);
+    assert_eq!(3, levels.len());
+    assert_eq!(
+        total,
+        levels[0].len(),
+        "The first level keeps every polygon."
+    );
+    assert!(levels[1].len() <= levels[0].len());
+    assert!(levels[1].iter().all(|polygon| polygon.area() >= 10f64));
+    assert!(levels[2].is_empty());
+}
+
+#[test]
+fn merge_adjacent_coplanar_group() {
+    let square = |x: f64, y: f64| {
+        polygonum::Polygon::from(vec![
+            point!(x, y, 0f64),
+            point!(x + 1f64, y, 0f64),
+            point!(x + 1f64, y + 1f64, 0f64),
+            point!(x, y + 1f64, 0f64),
+        ])
+    };
+    let merged = polygonum::merge_adjacent_coplanar_group(vec![
+        square(0f64, 0f64),
+        square(1f64, 0f64),
+        square(1f64, 1f64),
+    ])
+    .unwrap();
+    assert!((merged.area() - 3f64).abs() < 1e-9);
+    assert_eq!(
+        9,
+        merged.iter().count(),
+        "The merged polygon keeps the vertices along its boundary."
+    );
+    assert!(
+        polygonum::merge_adjacent_coplanar_group(vec![square(0f64, 0f64), square(2f64, 0f64)])
+            .is_none(),
+        "Disconnected polygons cannot be merged."
+    );
+}
+
+#[test]
+fn perimeter() {
+    let square = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    assert_eq!(4f64, square.perimeter());
+    let trapezoid = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(6f64, 0f64, 0f64),
+        point!(3f64, 4f64, 0f64),
+        point!(0f64, 4f64, 0f64),
+    ]);
+    assert_eq!(18f64, trapezoid.perimeter());
+    let tilted = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(3f64, 0f64, 4f64),
+        point!(3f64, 2f64, 4f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    assert_eq!(14f64, tilted.perimeter());
+}
+
+#[test]
+fn centroid() {
+    // computes the unweighted mean of the vertices skipping the closing one
+    let mean = |polygon: &polygonum::Polygon| {
+        let vertices = polygon.iter().skip(1).collect::<Vec<_>>();
+        let n = vertices.len() as f64;
+        point!(
+            vertices.iter().map(|point| point.x).sum::<f64>() / n,
+            vertices.iter().map(|point| point.y).sum::<f64>() / n,
+            vertices.iter().map(|point| point.z).sum::<f64>() / n
+        )
+    };
+    let distance = |a: polygonum::Point, b: polygonum::Point| {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+    };
+    // a regular hexagon on a tilted plane
+    let hexagon = polygonum::Polygon::from(
+        (0..6)
+            .map(|index| {
+                let angle = index as f64 * std::f64::consts::PI / 3f64;
+                point!(angle.cos(), angle.sin(), 1f64 + angle.cos() / 2f64)
+            })
+            .collect(),
+    );
+    assert!(
+        distance(hexagon.centroid(), mean(&hexagon)) < 1e-9,
+        "The weighted and unweighted centers agree on regular polygons."
+    );
+    // an irregular pentagon with vertices clustered on one side
+    let pentagon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 2f64),
+        point!(10f64, 0f64, 2f64),
+        point!(10f64, 1f64, 2f64),
+        point!(9.5, 1f64, 2f64),
+        point!(0f64, 10f64, 2f64),
+    ]);
+    assert!(
+        distance(pentagon.centroid(), mean(&pentagon)) > 0.1,
+        "The weighted and unweighted centers differ on irregular polygons."
+    );
+    assert_eq!(2f64, pentagon.centroid().z);
+}
+
+#[test]
+fn normal() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(0f64, 2f64, 0f64),
+        point!(2f64, 2f64, 2f64),
+        point!(2f64, 0f64, 2f64),
+    ]);
+    let normal: polygonum::Vector = polygon.normal();
+    assert!(normal.z > 0f64, "The normal always points upwards.");
+    assert!((normal.norm() - 2f64 * polygon.area()).abs() < 1e-9);
+    assert!((normal.x + normal.z).abs() < 1e-9 && normal.y.abs() < 1e-9);
+}
+
+#[test]
+fn is_convex() {
+    let square = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 1f64),
+        point!(1f64, 1f64, 1f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    assert!(square.is_convex());
+    let shape = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 0f64),
+        point!(2f64, 1f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(1f64, 2f64, 0f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    assert!(!shape.is_convex(), "An L-shape is not convex.");
+    let triangle = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(0f64, 0f64, 1f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    assert!(triangle.is_convex(), "A vertical triangle is convex.");
+    let collinear = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(2f64, 0f64, 0f64),
+        point!(2f64, 2f64, 0f64),
+    ]);
+    assert!(!collinear.is_convex(), "Collinear vertices are not convex.");
+}
+
+#[test]
+fn counts() {
+    let polygons = polygonum::polygonalize(dataset!("house.geojson"), true, 0.01);
+    assert!(polygons.iter().all(|polygon| {
+        polygon.vertex_count() == polygon.edge_count()
+            && polygon.vertex_count() + 1 == polygon.iter().count()
+    }));
+}
+
+#[test]
+fn edges() {
+    for polygon in polygonum::polygonalize(dataset!("house.geojson"), true, 0.01) {
+        let edges = polygon.edges().collect::<Vec<_>>();
+        assert_eq!(polygon.vertex_count(), edges.len());
+        let rebuilt = polygonum::polygonalize(&edges, false, 0f64);
+        assert!(
+            rebuilt.len() == 1 && rebuilt[0] == polygon,
+            "The edges of a polygon describe the same polygon."
+        );
+    }
+}
+
+#[test]
+fn bounding_box() {
+    let (min, max) = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ])
+    .bounding_box();
+    assert_eq!((0f64, 0f64, 1f64, 1f64), (min.x, min.y, max.x, max.y));
+    assert!(min.z.is_nan() && max.z.is_nan());
+    let (min, max) = polygonum::Polygon::from(vec![
+        point!(2f64, 0f64, 1f64),
+        point!(4f64, 2f64, 2f64),
+        point!(2f64, 4f64, 3f64),
+        point!(0f64, 2f64, 2f64),
+    ])
+    .bounding_box();
+    assert_eq!((0f64, 0f64, 4f64, 4f64), (min.x, min.y, max.x, max.y));
+}
+
+#[test]
+fn is_planar() {
+    let triangle = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 3f64),
+        point!(0f64, 1f64, 7f64),
+    ]);
+    assert!(triangle.is_planar(1e-12));
+    let saddle = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 1f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 1f64),
+    ]);
+    assert!(!saddle.is_planar(0.1));
+    let polygons = polygonum::polygonalize(
+        &[
+            segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+            segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+            segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+            segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+            segment!(10f64, 10f64, 5f64 => 20f64, 10f64, 0f64),
+            segment!(20f64, 10f64, 0f64 => 20f64, 0f64, 0f64),
+            segment!(20f64, 0f64, 0f64 => 10f64, 0f64, 5f64),
+        ],
+        true,
+        0.01,
+    );
+    assert!(
+        polygons.len() == 2 && polygons.iter().all(|polygon| polygon.is_planar(1e-9)),
+        "The polygons constructed from planar faces are planar."
+    );
+}
+
+#[test]
+fn triangulate() {
+    // computes the area of a triangle in space
+    let area = |[a, b, c]: [polygonum::Point; 3]| {
+        let (u, v) = (
+            (b.x - a.x, b.y - a.y, b.z - a.z),
+            (c.x - a.x, c.y - a.y, c.z - a.z),
+        );
+        let cross = (
+            u.1 * v.2 - u.2 * v.1,
+            u.2 * v.0 - u.0 * v.2,
+            u.0 * v.1 - u.1 * v.0,
+        );
+        (cross.0 * cross.0 + cross.1 * cross.1 + cross.2 * cross.2).sqrt() / 2f64
+    };
+    // a vertical comb which requires proper ear detection
+    let comb = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(5f64, 0f64, 0f64),
+        point!(5f64, 0f64, 3f64),
+        point!(4f64, 0f64, 1f64),
+        point!(3f64, 0f64, 3f64),
+        point!(2f64, 0f64, 1f64),
+        point!(1f64, 0f64, 3f64),
+        point!(0f64, 0f64, 1f64),
+    ]);
+    let triangles = comb.triangulate();
+    assert_eq!(6, triangles.len());
+    assert!(
+        (triangles.into_iter().map(area).sum::<f64>() - comb.area()).abs() < 1e-9,
+        "The triangles tessellate the polygon exactly."
+    );
+    for polygon in polygonum::polygonalize(dataset!("house.geojson"), true, 0.01) {
+        let triangles = polygon.triangulate();
+        assert_eq!(polygon.vertex_count() - 2, triangles.len());
+        // the polygons of real data are not exactly planar
+        assert!(
+            (triangles.into_iter().map(area).sum::<f64>() - polygon.area()).abs()
+                < 1e-4 * polygon.area()
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let point = point!(1f64, 2.5, -3f64);
+    assert_eq!(
+        r#"{"x":1.0,"y":2.5,"z":-3.0}"#,
+        serde_json::to_string(&point).unwrap()
+    );
+    let segment = segment!(0f64, 0f64, 0f64 => 1f64, 2.5, -3f64);
+    assert_eq!(
+        segment,
+        serde_json::from_str::<polygonum::Segment>(&serde_json::to_string(&segment).unwrap())
+            .unwrap()
+    );
+    let points = [point, point!(f64::MIN_POSITIVE, -0f64, 1e300)];
+    assert_eq!(
+        points.to_vec(),
+        bincode::deserialize::<Vec<polygonum::Point>>(
+            &bincode::serialize(&points.to_vec()).unwrap()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        segment,
+        bincode::deserialize::<polygonum::Segment>(&bincode::serialize(&segment).unwrap()).unwrap()
+    );
+    for polygon in polygonum::polygonalize(dataset!("house.geojson"), true, 0.01) {
+        let serialized = serde_json::to_string(&polygon).unwrap();
+        let deserialized = serde_json::from_str::<polygonum::Polygon>(&serialized).unwrap();
+        assert!(deserialized == polygon);
+        assert!(deserialized.iter().eq(polygon.iter()));
+    }
+}
+
+#[test]
+fn fmt() {
+    let triangle = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+    ]);
+    assert_eq!("(1.0, 0.5, -2.0)", format!("{}", point!(1f64, 0.5, -2f64)));
+    assert_eq!(
+        "Point { x: 1.0, y: 0.5, z: -2.0 }",
+        format!("{:?}", point!(1f64, 0.5, -2f64))
+    );
+    assert_eq!(
+        "[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0)] area=0.5",
+        format!("{triangle}")
+    );
+    assert_eq!(
+        "Polygon { vertices: [Point { x: 0.0, y: 0.0, z: 0.0 }, Point { x: 1.0, y: 0.0, z: 0.0 }, Point { x: 1.0, y: 1.0, z: 0.0 }] }",
+        format!("{triangle:?}")
+    );
+}
+
+#[test]
+fn winding_order() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+    ]);
+    assert_eq!(
+        polygonum::WindingOrder::CounterClockwise,
+        polygon.winding_order(),
+        "Clockwise vertices are reordered on construction."
+    );
+}
+
+#[test]
+fn flip() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 1f64),
+        point!(2f64, 3f64, 1f64),
+        point!(0f64, 3f64, 0f64),
+    ]);
+    let flipped = polygon.flip();
+    assert_eq!(-polygon.normal().z, flipped.normal().z);
+    assert_eq!(polygon.area(), flipped.area());
+    assert_eq!(polygonum::WindingOrder::Clockwise, flipped.winding_order());
+    let mut twice = flipped.flip();
+    assert!(
+        twice.iter().eq(polygon.iter()),
+        "Flipping twice is identity."
+    );
+    twice.flip_in_place();
+    assert!(twice.iter().eq(flipped.iter()));
+}
+
+#[test]
+fn project_to_xy() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(3f64, 0f64, 4f64),
+        point!(3f64, 2f64, 4f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    let footprint = polygon.project_to_xy();
+    assert_eq!(4, footprint.len());
+    // computes the area with the shoelace formula
+    let area = (0..footprint.len())
+        .map(|index| {
+            let ((ax, ay), (bx, by)) = (footprint[index], footprint[(index + 1) % footprint.len()]);
+            ax * by - bx * ay
+        })
+        .sum::<f64>()
+        .abs()
+        / 2f64;
+    assert!((area - polygon.area_projected()).abs() < 1e-9);
+    assert!((area - 6f64).abs() < 1e-9);
+}
+
+#[test]
+fn elevation_range() {
+    let floor = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 5f64),
+        point!(1f64, 0f64, 5f64),
+        point!(1f64, 1f64, 5f64),
+    ]);
+    assert_eq!((5f64, 5f64), floor.elevation_range());
+    let roof = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 3f64),
+        point!(4f64, 0f64, 3f64),
+        point!(4f64, 2f64, 6f64),
+        point!(0f64, 2f64, 6f64),
+    ]);
+    assert_eq!((3f64, 6f64), roof.elevation_range());
+}
+
+#[test]
+fn orientation() {
+    let floor = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    let wall = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 0f64, 1f64),
+        point!(0f64, 0f64, 1f64),
+    ]);
+    let roof = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 1f64),
+        point!(1f64, 1f64, 1f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    assert!(floor.is_horizontal(0.01) && !floor.is_vertical(0.01));
+    assert!(!wall.is_horizontal(0.01) && wall.is_vertical(0.01));
+    assert!(!roof.is_horizontal(0.01) && !roof.is_vertical(0.01));
+    assert!(roof.is_horizontal(0.8) && roof.is_vertical(0.8));
+}
+
+#[test]
+fn slope_angle() {
+    let rectangle = |height: f64| {
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 0f64),
+            point!(0f64, 2f64, 0f64),
+            point!(1f64, 2f64, height),
+            point!(1f64, 0f64, height),
+        ])
+    };
+    assert_eq!(0f64, rectangle(0f64).slope_angle());
+    assert!((rectangle(1f64).slope_angle() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+    let wall = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(0f64, 2f64, 0f64),
+        point!(0f64, 2f64, 1f64),
+        point!(0f64, 0f64, 1f64),
+    ]);
+    assert_eq!(std::f64::consts::FRAC_PI_2, wall.slope_angle());
+}
+
+#[test]
+fn surface_area_ratio() {
+    let rectangle = |height: f64| {
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 0f64),
+            point!(0f64, 2f64, 0f64),
+            point!(1f64, 2f64, height),
+            point!(1f64, 0f64, height),
+        ])
+    };
+    assert_eq!(1f64, rectangle(0f64).surface_area_ratio());
+    assert!((rectangle(3f64.sqrt()).surface_area_ratio() - 2f64).abs() < 1e-12);
+    let wall = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(0f64, 2f64, 0f64),
+        point!(0f64, 2f64, 1f64),
+    ]);
+    assert_eq!(f64::INFINITY, wall.surface_area_ratio());
+}
+
+#[test]
+fn distance_to_point() {
+    // a 45 degrees tilted square
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 2f64),
+        point!(2f64, 2f64, 2f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    let root = 2f64.sqrt();
+    // interior
+    assert!((polygon.distance_to_point(&point!(0f64, 1f64, 2f64)) - root).abs() < 1e-12);
+    assert!(polygon.distance_to_point(&point!(1f64, 1f64, 1f64)).abs() < 1e-12);
+    // boundary
+    assert!(polygon.distance_to_point(&point!(2f64, 1f64, 2f64)).abs() < 1e-12);
+    // exterior, closest to a side and to a corner
+    assert!((polygon.distance_to_point(&point!(1f64, 3f64, 1f64)) - 1f64).abs() < 1e-12);
+    assert!((polygon.distance_to_point(&point!(3f64, 3f64, 2f64)) - root).abs() < 1e-12);
+}
+
+#[test]
+fn intersects() {
+    let square = |x: f64, y: f64, size: f64| {
+        polygonum::Polygon::from(vec![
+            point!(x, y, 0f64),
+            point!(x + size, y, 0f64),
+            point!(x + size, y + size, 1f64),
+            point!(x, y + size, 1f64),
+        ])
+    };
+    // partial overlap
+    assert!(square(0f64, 0f64, 2f64).intersects(&square(1f64, 1f64, 2f64)));
+    // nested polygons, also sharing part of the boundary
+    assert!(square(0f64, 0f64, 4f64).intersects(&square(1f64, 1f64, 1f64)));
+    assert!(square(1f64, 1f64, 1f64).intersects(&square(0f64, 0f64, 4f64)));
+    assert!(square(0f64, 0f64, 4f64).intersects(&square(0f64, 0f64, 2f64)));
+    // identical polygons
+    assert!(square(0f64, 0f64, 1f64).intersects(&square(0f64, 0f64, 1f64)));
+    // touching along a side or at a corner
+    assert!(!square(0f64, 0f64, 1f64).intersects(&square(1f64, 0f64, 1f64)));
+    assert!(!square(0f64, 0f64, 1f64).intersects(&square(1f64, 1f64, 1f64)));
+    // disjoint
+    assert!(!square(0f64, 0f64, 1f64).intersects(&square(3f64, 0f64, 1f64)));
+}
+
+#[test]
+fn split() {
+    // a tilted polygon shaped like an U
+    let shape = || {
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 0f64),
+            point!(3f64, 0f64, 0f64),
+            point!(3f64, 3f64, 3f64),
+            point!(2f64, 3f64, 3f64),
+            point!(2f64, 1f64, 1f64),
+            point!(1f64, 1f64, 1f64),
+            point!(1f64, 3f64, 3f64),
+            point!(0f64, 3f64, 3f64),
+        ])
+    };
+    let area = shape().area();
+    let normal = polygonum::Vector {
+        x: 0f64,
+        y: 1f64,
+        z: 0f64,
+    };
+    let (above, below) = shape().split(normal, point!(0f64, 2f64, 0f64));
+    assert_eq!(2, above.len(), "The prongs of the U are split apart.");
+    assert_eq!(1, below.len());
+    assert!(
+        (above
+            .iter()
+            .chain(&below)
+            .map(|part| part.area())
+            .sum::<f64>()
+            - area)
+            .abs()
+            < 1e-9,
+        "The parts cover the original polygon."
+    );
+    let (above, below) = shape().split(normal, point!(0f64, 0f64, 0f64));
+    assert!(
+        above.len() == 1 && below.is_empty(),
+        "Touching vertices lie above."
+    );
+    let (above, below) = shape().split(normal, point!(0f64, 5f64, 0f64));
+    assert!(above.is_empty() && below.len() == 1);
+}
+
+#[test]
+fn clip_to_bbox() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 2f64),
+        point!(2f64, 2f64, 2f64),
+        point!(0f64, 2f64, 0f64),
+    ]);
+    let clipped = polygon
+        .clip_to_bbox(point!(1f64, -1f64, 0f64), point!(3f64, 1f64, 0f64))
+        .unwrap();
+    assert!((clipped.area_projected() - 1f64).abs() < 1e-9);
+    assert!(
+        clipped
+            .iter()
+            .all(|vertex| (vertex.z - vertex.x).abs() < 1e-9),
+        "Elevation is interpolated."
+    );
+    let whole = polygon
+        .clip_to_bbox(point!(-1f64, -1f64, 0f64), point!(3f64, 3f64, 0f64))
+        .unwrap();
+    assert!(whole == polygon, "A polygon within the box is kept as is.");
+    assert!(
+        polygon
+            .clip_to_bbox(point!(2f64, 2f64, 0f64), point!(3f64, 3f64, 0f64))
+            .is_none(),
+        "Touching at a single vertex yields nothing."
+    );
+    assert!(polygon
+        .clip_to_bbox(point!(5f64, 5f64, 0f64), point!(6f64, 6f64, 0f64))
+        .is_none());
+}
+
+#[cfg(feature = "wkt")]
+#[test]
+fn wkt() {
+    let square = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    let text = "POLYGON Z ((0.0 0.0 0.0, 1.0 0.0 0.0, 1.0 1.0 0.0, 0.0 1.0 0.0, 0.0 0.0 0.0))";
+    assert_eq!(text, square.to_wkt());
+    assert_eq!(text, polygonum::Polygon::from_wkt(text).unwrap().to_wkt());
+    assert!(polygonum::Polygon::from_wkt(&square.to_wkt()).unwrap() == square);
+    assert!(polygonum::Polygon::from_wkt(" polygon z((0.5 1.5 -2,3 0 1e-3, 2 2 2)) ").is_ok());
+    assert_eq!(
+        Err(polygonum::WktError::UnexpectedTag),
+        polygonum::Polygon::from_wkt("LINESTRING Z (0 0 0, 1 1 1)")
+    );
+    assert_eq!(
+        Err(polygonum::WktError::InvalidCoordinates("1 0".to_string())),
+        polygonum::Polygon::from_wkt("POLYGON Z ((0 0 0, 1 0, 1 1 0, 0 0 0))")
+    );
+    assert_eq!(
+        Err(polygonum::WktError::DegeneratePolygon),
+        polygonum::Polygon::from_wkt("POLYGON Z ((0 0 0, 1 1 1, 2 2 2, 0 0 0))")
+    );
+}
+
+#[cfg(feature = "geojson")]
+#[test]
+fn geojson() {
+    let polygons = polygonum::polygonalize(dataset!("house.geojson"), false, 0f64);
+    let collection = serde_json::from_str::<serde_json::Value>(
+        &polygonum::polygons_to_geojson_feature_collection(&polygons),
+    )
+    .unwrap();
+    assert_eq!("FeatureCollection", collection["type"]);
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(polygons.len(), features.len());
+    // reconstructs each polygon from its single ring
+    for (polygon, feature) in polygons.iter().zip(features) {
+        assert_eq!("Polygon", feature["geometry"]["type"]);
+        let ring = feature["geometry"]["coordinates"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|position| {
+                let position = position.as_array().unwrap();
+                point!(
+                    position[0].as_f64().unwrap(),
+                    position[1].as_f64().unwrap(),
+                    position[2].as_f64().unwrap()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ring.first(), ring.last(), "The ring is closed.");
+        assert!(polygonum::Polygon::from(ring[1..].to_vec()) == *polygon);
+    }
+}
+
+#[test]
+fn conversions() {
+    let point = point!(1f64, -2f64, 3.5);
+    assert_eq!(point, polygonum::Point::from_array([1f64, -2f64, 3.5]));
+    assert_eq!(point, polygonum::Point::from([1f64, -2f64, 3.5]));
+    assert_eq!(point, polygonum::Point::from((1f64, -2f64, 3.5)));
+    assert_eq!([1f64, -2f64, 3.5], <[f64; 3]>::from(point));
+    let (x, y, z): (f64, f64, f64) = point.into();
+    assert_eq!((1f64, -2f64, 3.5), (x, y, z));
+}
+
+#[test]
+fn distance() {
+    let (origin, diagonal) = (point!(0f64, 0f64, 0f64), point!(1f64, 1f64, 1f64));
+    assert!((polygonum::Point::distance(&origin, &diagonal) - 3f64.sqrt()).abs() < 1e-12);
+    assert_eq!(3f64, origin.distance_squared(&diagonal));
+    let (a, b) = (point!(1.5, -2f64, 7f64), point!(-4f64, 0.25, 3f64));
+    assert_eq!(a.distance(&b), a.distance_squared(&b).sqrt());
+    assert_eq!(a.distance(&b), b.distance(&a));
+}
+
+#[test]
+fn segment_length_and_midpoint() {
+    assert_eq!(
+        5f64,
+        polygonum::segment_length(segment!(0f64, 0f64, 0f64 => 3f64, 4f64, 0f64))
+    );
+    assert_eq!(
+        point!(1f64, 0f64, 3f64),
+        polygonum::segment_midpoint(segment!(0f64, 0f64, 0f64 => 2f64, 0f64, 6f64))
+    );
+}
+
+#[test]
+fn segment_closest_point() {
+    use polygonum::plane::{segment_closest_point, segment_distance_to_point};
+
+    let segment = segment!(0f64, 0f64, 1f64 => 4f64, 0f64, 1f64);
+    assert_eq!(
+        point!(2f64, 0f64, 1f64),
+        segment_closest_point(segment, point!(2f64, 0f64, 4f64)),
+        "The projection of a point above the midpoint."
+    );
+    assert_eq!(
+        3f64,
+        segment_distance_to_point(segment, point!(2f64, 0f64, 4f64))
+    );
+    assert_eq!(
+        segment.1,
+        segment_closest_point(segment, point!(7f64, 4f64, 1f64)),
+        "The projection is clamped to the endpoints."
+    );
+    assert_eq!(
+        5f64,
+        segment_distance_to_point(segment, point!(7f64, 4f64, 1f64))
+    );
+}
+
+#[test]
+fn segment_canonical() {
+    let segment = segment!(1f64, 0f64, 0f64 => 0f64, 5f64, 0f64);
+    let flipped = (segment.1, segment.0);
+    assert_eq!(flipped, polygonum::segment_canonical(segment));
+    assert_eq!(flipped, polygonum::segment_canonical(flipped));
+    assert!(polygonum::segment_canonical_eq(segment, flipped));
+    assert!(!polygonum::segment_canonical_eq(
+        segment,
+        segment!(1f64, 0f64, 0f64 => 0f64, 5f64, 1f64)
+    ));
+}
+
+#[test]
+fn lerp_clamped() {
+    let (a, b) = (point!(0.1, -3f64, 7f64), point!(2.3, 5f64, -1f64));
+    assert_eq!(a, a.lerp(&b, 0f64));
+    assert_eq!(b, a.lerp(&b, 1f64));
+    assert_eq!(polygonum::segment_midpoint((a, b)), a.lerp(&b, 0.5));
+    assert_eq!(
+        point!(4.5, 13f64, -9f64),
+        a.lerp(&b, 2f64),
+        "Interpolation is not clamped."
+    );
+    assert_eq!(b, a.lerp_clamped(&b, 2f64));
+    assert_eq!(a, a.lerp_clamped(&b, -1f64));
+}
+
+#[test]
+fn project_onto_segment() {
+    let segment = segment!(0f64, 0f64, 0f64 => 2f64, 2f64, 0f64);
+    let on = point!(0.5, 0.5, 0f64);
+    assert_eq!(on, on.project_onto_segment(segment));
+    assert_eq!(
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 2f64, 3f64).project_onto_segment(segment)
+    );
+}
+
+#[test]
+fn connected_components() {
+    let graph = polygonum::PointGraph::from(&[
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(5f64, 5f64, 0f64 => 6f64, 5f64, 0f64),
+    ]);
+    let mut components = graph
+        .connected_components()
+        .map(|component| component.len())
+        .collect::<Vec<_>>();
+    components.sort();
+    assert_eq!(vec![2, 3], components);
+}
+
+#[test]
+fn connected_component_count() {
+    let quadrilateral = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+    ];
+    assert_eq!(
+        1,
+        polygonum::PointGraph::from(&quadrilateral).connected_component_count()
+    );
+    let triangles = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(5f64, 0f64, 0f64 => 6f64, 0f64, 0f64),
+        segment!(6f64, 0f64, 0f64 => 6f64, 1f64, 0f64),
+        segment!(6f64, 1f64, 0f64 => 5f64, 0f64, 0f64),
+    ];
+    assert_eq!(
+        2,
+        polygonum::PointGraph::from(&triangles).connected_component_count()
+    );
+    assert_eq!(
+        0,
+        polygonum::PointGraph::from(&[]).connected_component_count()
+    );
+}
+
+#[test]
+fn node_and_edge_count() {
+    let square = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(0f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+    ];
+    let counts = polygonum::Pipeline::from(&square)
+        .apply(|graph| std::iter::once((graph.node_count(), graph.edge_count())));
+    // each of the two endpoints of the diagonal links its three ingoing to two outgoing segments
+    assert_eq!(vec![(10, 2 * 6 + 2 * 2)], counts);
+}
+
+#[test]
+fn clone() {
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 2f64, 2f64, 0f64),
+    ];
+    let graph = polygonum::PointGraph::from(&segments);
+    let pruned = graph.clone().prune();
+    assert!(
+        pruned != graph,
+        "Pruning the clone leaves the original untouched."
+    );
+    assert!(graph == polygonum::PointGraph::from(&segments));
+    assert_eq!(4, graph.to_dot_with_coordinates(0).matches(" -- ").count());
+    assert_eq!(3, pruned.to_dot_with_coordinates(0).matches(" -- ").count());
+    let clones = polygonum::Pipeline::from(&segments).apply(|graph| {
+        let clone = graph.clone();
+        std::iter::once(clone == graph && clone.edge_count() == graph.edge_count())
+    });
+    assert_eq!(vec![true], clones);
+}
+
+#[test]
+fn debug() {
+    let graph = polygonum::PointGraph::weighted(&[
+        (segment!(1f64, 0f64, 0f64 => 0f64, 0f64, 0f64), 2f32),
+        (segment!(0f64, 0f64, 0f64 => 0f64, 1f64, 0f64), 1f32),
+    ])
+    .unwrap();
+    let (a, b, c) = (
+        "Point { x: 0.0, y: 0.0, z: 0.0 }",
+        "Point { x: 0.0, y: 1.0, z: 0.0 }",
+        "Point { x: 1.0, y: 0.0, z: 0.0 }",
+    );
+    assert_eq!(
+        format!(
+            "PointGraph {{ adjacencies: {{{a}: {{{b}, {c}}}, {b}: {{{a}}}, {c}: {{{a}}}}}, \
+            weights: {{({a}, {b}): 1.0, ({a}, {c}): 2.0, ({b}, {a}): 1.0, ({c}, {a}): 2.0}} }}"
+        ),
+        format!("{:?}", graph)
+    );
+    let square = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+    ];
+    let outputs =
+        polygonum::Pipeline::from(&square).apply(|graph| std::iter::once(format!("{:?}", graph)));
+    let repeated =
+        polygonum::Pipeline::from(&square).apply(|graph| std::iter::once(format!("{:?}", graph)));
+    assert_eq!(outputs, repeated, "The output is stable.");
+    assert!(
+        outputs[0].starts_with("SegmentGraph { adjacencies: {(Point { x: 0.0, y: 0.0, z: 0.0 }")
+    );
+}
+
+#[test]
+fn long_chain() {
+    let chain = (0..50000)
+        .map(|i| segment!(i as f64, 0f64, 0f64 => (i + 1) as f64, 0f64, 0f64))
+        .collect::<Vec<_>>();
+    let pipeline = polygonum::Pipeline::from(&chain);
+    assert!(pipeline.apply(|graph| std::iter::once(graph.node_count())) == vec![0]);
+    // closing the chain keeps all of its points after pruning
+    let mut cycle = chain;
+    cycle.push(segment!(50000f64, 0f64, 0f64 => 0f64, 1f64, 0f64));
+    cycle.push(segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64));
+    assert_eq!(
+        1,
+        polygonum::PointGraph::from(&cycle).connected_component_count()
+    );
+    let nodes = polygonum::Pipeline::from(&cycle)
+        .partition()
+        .apply(|graph| std::iter::once(graph.node_count()));
+    assert_eq!(vec![2 * 50002], nodes);
+}
+
+#[test]
+fn stats() {
+    // a grid of 3 by 3 points
+    let grid = (0..3)
+        .flat_map(|i| {
+            (0..2).flat_map(move |j| {
+                let (i, j) = (i as f64, j as f64);
+                [
+                    segment!(i, j, 0f64 => i, j + 1f64, 0f64),
+                    segment!(j, i, 0f64 => j + 1f64, i, 0f64),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        polygonum::GraphStats {
+            node_count: 9,
+            edge_count: 12,
+            component_count: 1,
+            max_degree: 4,
+            avg_degree: 24f64 / 9f64,
+            pruned_node_count: 0,
+        },
+        polygonum::stats(&grid)
+    );
+    // a square whose corners have teeth, which are pruned, and a detached comb, which is pruned entirely
+    let mut comb = vec![
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(0f64, 0f64, 0f64 => -1f64, -1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 2f64, 2f64, 0f64),
+    ];
+    comb.extend((0..4).flat_map(|i| {
+        let x = 10f64 + i as f64;
+        [
+            segment!(x, 0f64, 0f64 => x + 1f64, 0f64, 0f64),
+            segment!(x, 0f64, 0f64 => x, 1f64, 0f64),
+        ]
+    }));
+    assert_eq!(
+        polygonum::GraphStats {
+            node_count: 4,
+            edge_count: 4,
+            component_count: 1,
+            max_degree: 2,
+            avg_degree: 2f64,
+            pruned_node_count: 2 + 9,
+        },
+        polygonum::stats(&comb)
+    );
+    assert_eq!(0f64, polygonum::stats(&[]).avg_degree);
+}
+
+#[test]
+fn snap_close_vertices() {
+    let triangle = |offset: f64| {
+        polygonum::PointGraph::from(&[
+            segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+            segment!(1f64 + offset, 0f64, 0f64 => 1f64, 1f64, 0f64),
+            segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        ])
+    };
+    let snapped = triangle(0.0005).snap_close_vertices(0.001);
+    assert_eq!(1, snapped.connected_component_count());
+    assert!(snapped
+        .to_dot_with_coordinates(5)
+        .contains("[label=\"1.00025,0.00000,0.00000\"]"));
+    assert_eq!(
+        3,
+        snapped.to_dot_with_coordinates(0).matches(" -- ").count()
+    );
+    assert_eq!(
+        3,
+        snapped
+            .prune()
+            .to_dot_with_coordinates(0)
+            .matches(" -- ")
+            .count()
+    );
+    let apart = triangle(0.002).snap_close_vertices(0.001);
+    assert!(
+        apart == triangle(0.002),
+        "Points farther than the tolerance are kept apart."
+    );
+}
+
+#[test]
+fn validate() {
+    let mut segments = vec![
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+    ];
+    assert_eq!(Ok(()), polygonum::PointGraph::from(&segments).validate());
+    assert_eq!(
+        Ok(()),
+        polygonum::PointGraph::from(dataset!("house.geojson"))
+            .prune()
+            .validate()
+    );
+    segments.push(segment!(1f64, 1f64, 0f64 => 1f64, 1f64, 0f64));
+    assert_eq!(
+        Err(vec![polygonum::GraphError::SelfLoop(point!(
+            1f64, 1f64, 0f64
+        ))]),
+        polygonum::PointGraph::from(&segments).validate()
+    );
+}
+
+#[test]
+fn vector_operators() {
+    let vector = |x: f64, y: f64, z: f64| polygonum::Vector { x, y, z };
+    let (a, b) = (vector(1f64, 2f64, 3f64), vector(-0.5, 4f64, 0f64));
+    assert_eq!(vector(0.5, 6f64, 3f64), a + b);
+    assert_eq!(vector(1.5, -2f64, 3f64), a - b);
+    assert_eq!(vector(2f64, 4f64, 6f64), a * 2f64);
+    assert_eq!(vector(-1f64, -2f64, -3f64), -a);
+    assert_eq!(
+        a,
+        a + vector(f64::EPSILON / 2f64, 0f64, 0f64),
+        "Equality is approximate."
+    );
+    assert_ne!(a, b);
+}
+
+#[test]
+fn rotate() {
+    let vector = |x: f64, y: f64, z: f64| polygonum::Vector { x, y, z };
+    let v = vector(1f64, 0f64, 0f64);
+    assert_eq!(
+        vector(0f64, 1f64, 0f64),
+        v.rotate(vector(0f64, 0f64, 2f64), std::f64::consts::FRAC_PI_2)
+    );
+    let w = vector(0.3, -1.2, 2.5);
+    let axis = vector(1f64, 1f64, -0.5);
+    assert_eq!(w, w.rotate(axis, 0f64));
+    assert_eq!(v, v.rotate(axis, std::f64::consts::TAU));
+    assert!((w.rotate(axis, std::f64::consts::TAU) - w).norm() < 1e-14);
+    assert!(
+        (w.rotate(axis, 1f64).norm() - w.norm()).abs() < 1e-14,
+        "Rotations keep the length."
+    );
+}
+
+#[test]
+fn angle_with() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    let vector = |x: f64, y: f64, z: f64| polygonum::Vector { x, y, z };
+    let (x, y) = (vector(2f64, 0f64, 0f64), vector(0f64, 3f64, 0f64));
+    assert!((x.angle_with(&y) - FRAC_PI_2).abs() < 1e-15);
+    assert_eq!(0f64, x.angle_with(&(x * 4f64)));
+    assert_eq!(PI, x.angle_with(&-x));
+    assert!(x.angle_with(&vector(0f64, 0f64, 0f64)).is_nan());
+    assert!(x.is_parallel(&-x, 1e-9) && x.is_parallel(&vector(1f64, 1e-12, 0f64), 1e-9));
+    assert!(!x.is_parallel(&y, 1e-9));
+    assert!(x.is_perpendicular(&y, 1e-9) && !x.is_perpendicular(&vector(1f64, 1f64, 0f64), 1e-9));
+    assert!(
+        !x.is_parallel(&vector(0f64, 0f64, 0f64), 1e-9),
+        "The zero vector has no direction."
+    );
+}
+
+#[test]
+fn project_point_onto_plane() {
+    use polygonum::plane::project_point_onto_plane;
+
+    let up = polygonum::Vector {
+        x: 0f64,
+        y: 0f64,
+        z: 3f64,
+    };
+    let origin = point!(0f64, 0f64, 2f64);
+    let on = point!(4f64, -1f64, 2f64);
+    assert_eq!(on, project_point_onto_plane(on, origin, up));
+    assert_eq!(
+        on,
+        project_point_onto_plane(point!(4f64, -1f64, 7f64), origin, up)
+    );
+    let tilted = polygonum::Vector {
+        x: 1f64,
+        y: 0f64,
+        z: 1f64,
+    };
+    let projection = project_point_onto_plane(point!(2f64, 0f64, 3f64), origin, tilted);
+    assert!(projection.distance(&point!(0.5, 0f64, 1.5)) < 1e-12);
+}
+
+#[test]
+fn best_fit_plane() {
+    use polygonum::plane::best_fit_plane;
+
+    // noisy samples of the plane z = 0.5x + 0.25y + 2
+    let points = (0..10)
+        .flat_map(|i| (0..10).map(move |j| (i as f64, j as f64)))
+        .map(|(x, y)| {
+            let noise = if (x + y) as i64 % 2 == 0 { 1e-3 } else { -1e-3 };
+            point!(x, y, 0.5 * x + 0.25 * y + 2f64 + noise)
+        })
+        .collect::<Vec<_>>();
+    let (origin, normal) = best_fit_plane(&points).unwrap();
+    assert!(origin.distance(&point!(4.5, 4.5, 5.375)) < 1e-9);
+    let expected = polygonum::Vector {
+        x: -0.5,
+        y: -0.25,
+        z: 1f64,
+    };
+    assert!(normal.angle_with(&expected) < 1e-3);
+    assert!((normal.norm() - 1f64).abs() < 1e-12);
+    assert!(best_fit_plane(&points[..2]).is_none());
+    let collinear = (0..5)
+        .map(|i| point!(i as f64, 2f64 * i as f64, 1f64))
+        .collect::<Vec<_>>();
+    assert!(best_fit_plane(&collinear).is_none());
+}
+
+#[test]
+fn dihedral_angle() {
+    use polygonum::plane::dihedral_angle;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    let floor = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    let flat = polygonum::Polygon::from(vec![
+        point!(1f64, 0f64, 0f64),
+        point!(2f64, 0f64, 0f64),
+        point!(2f64, 1f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+    ]);
+    assert!((dihedral_angle(&floor, &flat).unwrap() - PI).abs() < 1e-12);
+    let ramp = polygonum::Polygon::from(vec![
+        point!(1f64, 0f64, 0f64),
+        point!(2f64, 0f64, 1f64),
+        point!(2f64, 1f64, 1f64),
+        point!(1f64, 1f64, 0f64),
+    ]);
+    assert!((dihedral_angle(&floor, &ramp).unwrap() - 3f64 * PI / 4f64).abs() < 1e-12);
+    let walls = [
+        vec![
+            point!(0f64, 0f64, 0f64),
+            point!(1f64, 0f64, 0f64),
+            point!(1f64, 0f64, 1f64),
+            point!(0f64, 0f64, 1f64),
+        ],
+        vec![
+            point!(0f64, 0f64, 0f64),
+            point!(0f64, 0f64, 1f64),
+            point!(0f64, 1f64, 1f64),
+            point!(0f64, 1f64, 0f64),
+        ],
+    ]
+    .map(polygonum::Polygon::from);
+    assert!((dihedral_angle(&walls[0], &walls[1]).unwrap() - FRAC_PI_2).abs() < 1e-12);
+    assert_eq!(None, dihedral_angle(&flat, &walls[1]));
+}
+
+#[test]
+fn convex_hull_2d() {
+    use polygonum::plane::convex_hull_2d;
+
+    let square = [
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 1f64),
+        point!(2f64, 2f64, 0f64),
+        point!(0f64, 2f64, 1f64),
+    ];
+    let mut points = vec![point!(1f64, 1f64, 5f64), point!(1f64, 0f64, 0f64)];
+    points.extend(square.iter().rev());
+    assert_eq!(square.to_vec(), convex_hull_2d(&points));
+    let hexagon = (0..6)
+        .map(|i| {
+            let angle = std::f64::consts::PI / 3f64 * i as f64 + std::f64::consts::PI;
+            point!(angle.cos(), angle.sin(), 0f64)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(hexagon, convex_hull_2d(&hexagon));
+    let pair = [point!(1f64, 0f64, 0f64), point!(0f64, 0f64, 0f64)];
+    assert_eq!(pair.to_vec(), convex_hull_2d(&pair));
+}
+
+#[test]
+fn orthogonal_basis() {
+    let normals = [
+        polygonum::Vector {
+            x: 0f64,
+            y: 0f64,
+            z: 1f64,
+        },
+        polygonum::Vector {
+            x: 1f64,
+            y: 0f64,
+            z: 0f64,
+        },
+        polygonum::Vector {
+            x: 0.3,
+            y: -2f64,
+            z: 0.7,
+        }
+        .normalize(),
+    ];
+    for normal in normals {
+        let (u, v) = polygonum::plane::orthogonal_basis(normal);
+        assert!(u.dot(&normal).abs() <= f64::EPSILON);
+        assert!(v.dot(&normal).abs() <= f64::EPSILON);
+        assert!(u.dot(&v).abs() <= f64::EPSILON);
+        assert!((u.norm() - 1f64).abs() <= f64::EPSILON && (v.norm() - 1f64).abs() <= f64::EPSILON);
+        // the determinant of the matrix whose rows are the three vectors
+        assert!((u.dot(&v.cross(&normal)) - 1f64).abs() <= 2f64 * f64::EPSILON);
+    }
+}
+
+#[test]
+fn polygonalize_with_progress() {
+    for parallelize in [false, true] {
+        let mut events = Vec::<polygonum::ProgressEvent>::new();
+        let polygons = polygonum::polygonalize_with_progress(
+            dataset!("compound.geojson"),
+            parallelize,
+            0.01,
+            |event| events.push(event),
+        );
+        assert_eq!(144, polygons.len());
+        for phase in [
+            polygonum::Phase::Pruning,
+            polygonum::Phase::Partitioning,
+            polygonum::Phase::Traversing,
+            polygonum::Phase::Filtering,
+        ] {
+            assert!(events.iter().any(|event| event.phase == phase));
+        }
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].progress <= pair[1].progress));
+        assert!(events
+            .iter()
+            .all(|event| (0f32..=1f32).contains(&event.progress)));
+        assert_eq!(1f32, events.last().unwrap().progress);
+    }
+}
+
+#[test]
+fn polygonalize_cancellable() {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    assert_eq!(
+        Ok(18),
+        polygonum::polygonalize_cancellable(dataset!("house.geojson"), true, 0.01, cancel.clone())
+            .map(|polygons| polygons.len())
+    );
+    // a large grid whose processing takes long
+    let size = 150;
+    let grid = (0..=size)
+        .flat_map(|i| {
+            (0..size).flat_map(move |j| {
+                let (i, j) = (i as f64, j as f64);
+                [
+                    segment!(i, j, 0f64 => i, j + 1f64, 0f64),
+                    segment!(j, i, 0f64 => j + 1f64, i, 0f64),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    cancel.store(true, Ordering::Relaxed);
+    for parallelize in [false, true] {
+        assert_eq!(
+            Err(polygonum::Cancelled),
+            polygonum::polygonalize_cancellable(&grid, parallelize, 0.01, cancel.clone())
+        );
+    }
+    // cancels while processing
+    for parallelize in [false, true] {
+        cancel.store(false, Ordering::Relaxed);
+        let flag = cancel.clone();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            flag.store(true, Ordering::Relaxed);
+        });
+        let result = polygonum::polygonalize_cancellable(&grid, parallelize, 0.01, cancel.clone());
+        canceller.join().unwrap();
+        assert_eq!(
+            Err(polygonum::Cancelled),
+            result.map(|polygons| polygons.len())
+        );
+    }
+}
+
+#[test]
+fn polygonalize_with_config() {
+    let config = polygonum::PolygonalizeConfig::default();
+    assert_eq!(
+        polygonum::polygonalize(dataset!("house.geojson"), true, 0.01).len(),
+        polygonum::polygonalize_with_config(dataset!("house.geojson"), config.clone()).len()
+    );
+    let areas = polygonum::polygonalize_with_config(
+        dataset!("house.geojson"),
+        config
+            .clone()
+            .parallelize(false)
+            .minimum_area(1f64)
+            .maximum_area(10f64),
+    )
+    .iter()
+    .map(|polygon| polygon.area_projected())
+    .collect::<Vec<_>>();
+    assert!(!areas.is_empty() && areas.iter().all(|area| (1f64..=10f64).contains(area)));
+    let polygons =
+        polygonum::polygonalize_with_config(dataset!("house.geojson"), config.minimum_vertices(5));
+    assert!(!polygons.is_empty() && polygons.iter().all(|polygon| polygon.vertex_count() >= 5));
+}
+
+#[test]
+fn snap_tolerance() {
+    // the second quadrilateral reaches the first through a slightly misplaced point
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 2f64, 0f64, 0f64),
+        segment!(2f64, 0f64, 0f64 => 2f64, 1f64, 0f64),
+        segment!(2f64, 1f64, 0f64 => 1f64 + 1e-7, 1f64, 0f64),
+    ];
+    let config = polygonum::PolygonalizeConfig::default();
+    assert_eq!(
+        1,
+        polygonum::polygonalize_with_config(&segments, config.clone()).len()
+    );
+    assert_eq!(
+        2,
+        polygonum::polygonalize_with_config(&segments, config.clone().snap_tolerance(1e-6)).len()
+    );
+    assert_eq!(
+        1,
+        polygonum::polygonalize_with_config(&segments, config.snap_tolerance(1e-30)).len(),
+        "A tiny tolerance does not overflow the grid of close points."
+    );
+}
+
+#[test]
+fn maximum_vertices() {
+    // a closed spiral of 100 vertices whose radius slowly grows
+    let vertices = (0..100)
+        .map(|i| {
+            let (angle, radius) = (
+                i as f64 * std::f64::consts::TAU / 100f64,
+                10f64 + i as f64 * 0.01,
+            );
+            point!(radius * angle.cos(), radius * angle.sin(), 0f64)
+        })
+        .collect::<Vec<_>>();
+    let segments = (0..vertices.len())
+        .map(|i| (vertices[i], vertices[(i + 1) % vertices.len()]))
+        .collect::<Vec<_>>();
+    let config = polygonum::PolygonalizeConfig::default();
+    let polygons =
+        polygonum::polygonalize_with_config(&segments, config.clone().maximum_vertices(200));
+    assert_eq!(
+        vec![100],
+        polygons
+            .iter()
+            .map(|polygon| polygon.vertex_count())
+            .collect::<Vec<_>>()
+    );
+    assert!(polygonum::polygonalize_with_config(&segments, config.maximum_vertices(50)).is_empty());
+}
+
+#[test]
+fn polygonalize_detailed() {
+    for (dataset, count) in [
+        (dataset!("house.geojson"), 18),
+        (dataset!("compound.geojson"), 144),
+        (dataset!("church.geojson"), 126),
+    ] {
+        let result =
+            polygonum::polygonalize_detailed(dataset, polygonum::PolygonalizeConfig::default());
+        assert_eq!(count, result.polygons.len());
+        assert_eq!(
+            polygonum::stats(dataset).component_count,
+            result.component_count
+        );
+        assert_eq!(
+            polygonum::stats(dataset).pruned_node_count,
+            result.pruned_node_count
+        );
+        // the polygons found by the traversal do not depend on the filters
+        let unfiltered = polygonum::polygonalize_detailed(
+            dataset,
+            polygonum::PolygonalizeConfig::default()
+                .parallelize(false)
+                .minimum_area(0f64),
+        );
+        assert_eq!(
+            result.discarded_count + result.polygons.len(),
+            unfiltered.discarded_count + unfiltered.polygons.len()
+        );
+        assert!(result.discarded_count >= unfiltered.discarded_count);
+    }
+}
+
+#[test]
+fn polygonalize_streaming() {
+    use std::collections::HashSet;
+
+    for dataset in [
+        dataset!("house.geojson"),
+        dataset!("compound.geojson"),
+        dataset!("church.geojson"),
+    ] {
+        let mut stream =
+            polygonum::polygonalize_streaming(dataset, polygonum::PolygonalizeConfig::default());
+        // the stream can be paused after the first polygon and resumed later
+        let first = stream.next().unwrap();
+        let streamed = std::iter::once(first).chain(stream).collect::<HashSet<_>>();
+        assert!(
+            streamed
+                == polygonum::polygonalize(dataset, true, 0.01)
+                    .into_iter()
+                    .collect()
+        );
+    }
+}
+
+#[test]
+fn polygonalize_with_orphans() {
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+        segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+        segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+        segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+        segment!(10f64, 10f64, 5f64 => 20f64, 10f64, 0f64),
+        segment!(20f64, 10f64, 0f64 => 20f64, 0f64, 0f64),
+    ];
+    let config = polygonum::PolygonalizeConfig::default();
+    let (polygons, orphans) = polygonum::polygonalize_with_orphans(&segments, config.clone());
+    assert_eq!(1, polygons.len());
+    assert_eq!(
+        segments[4..].to_vec(),
+        orphans,
+        "The incomplete side is orphaned."
+    );
+    // snapping joins the incomplete side to the polygon
+    let mut snapped = segments.to_vec();
+    snapped.push(segment!(20f64, 0f64, 0f64 => 10f64, 1e-7, 5f64));
+    let (polygons, orphans) =
+        polygonum::polygonalize_with_orphans(&snapped, config.snap_tolerance(1e-6));
+    assert_eq!(2, polygons.len());
+    assert!(orphans.is_empty());
+}
+
+#[test]
+fn polygonalize_with_strategies() {
+    use std::collections::HashSet;
+
+    for dataset in [dataset!("house.geojson"), dataset!("compound.geojson")] {
+        let polygons = polygonum::polygonalize_with_strategies(
+            dataset,
+            &mut polygonum::strategy::defaults(),
+            polygonum::PolygonalizeConfig::default(),
+        );
+        assert!(
+            polygons.into_iter().collect::<HashSet<_>>()
+                == polygonum::polygonalize(dataset, true, 0.01)
+                    .into_iter()
+                    .collect()
+        );
+    }
+}
+
+#[test]
+fn minimum_angle_strategy() {
+    use std::collections::HashSet;
+
+    // a flat grid of unit squares
+    let size = 6;
+    let grid = (0..=size)
+        .flat_map(|i| {
+            (0..size).flat_map(move |j| {
+                let (i, j) = (i as f64, j as f64);
+                [
+                    segment!(i, j, 0f64 => i, j + 1f64, 0f64),
+                    segment!(j, i, 0f64 => j + 1f64, i, 0f64),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    let config = polygonum::PolygonalizeConfig::default();
+    let polygons = polygonum::polygonalize_with_strategies(
+        &grid,
+        &mut [polygonum::MinimumAngleStrategy::default()],
+        config.clone(),
+    );
+    assert_eq!(36, polygons.len());
+    assert!(
+        polygons.into_iter().collect::<HashSet<_>>()
+            == polygonum::polygonalize_with_config(&grid, config)
+                .into_iter()
+                .collect()
+    );
+}
+
+#[test]
+fn max_depth() {
+    // a long closed chain around a circle
+    let count = 2000;
+    let points = (0..count)
+        .map(|index| {
+            let angle = std::f64::consts::TAU * index as f64 / count as f64;
+            polygonum::Point::from((100f64 * angle.cos(), 100f64 * angle.sin(), 0f64))
+        })
+        .collect::<Vec<_>>();
+    let chain = (0..count)
+        .map(|index| (points[index], points[(index + 1) % count]))
+        .collect::<Vec<_>>();
+    let config = polygonum::PolygonalizeConfig::default();
+    assert!(
+        polygonum::polygonalize_with_config(&chain, config.clone().max_depth(100)).is_empty(),
+        "The chain is too long to be closed."
+    );
+    // the polygons with more sides than the limit are missed
+    let polygons =
+        polygonum::polygonalize_with_config(dataset!("house.geojson"), config.max_depth(4));
+    assert!(!polygons.is_empty());
+    assert!(polygons.iter().all(|polygon| polygon.vertex_count() <= 4));
+}
+
+#[test]
+fn traversal_stats() {
+    let segments = [
+        segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+        segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+        segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+        segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+        segment!(10f64, 10f64, 5f64 => 20f64, 10f64, 0f64),
+        segment!(20f64, 10f64, 0f64 => 20f64, 0f64, 0f64),
+        segment!(20f64, 0f64, 0f64 => 10f64, 0f64, 5f64),
+    ];
+    let results = polygonum::Pipeline::from(&segments)
+        .apply(|graph| std::iter::once(polygonum::traversal::traverse_with_stats(&graph)));
+    assert_eq!(1, results.len());
+    let (polygons, stats) = &results[0];
+    assert!(stats.paths_explored > 0);
+    assert!(stats.polygons_found >= polygons.len());
+    assert!(polygons.len() >= polygonum::polygonalize(&segments, true, 0.01).len());
+    assert!((3..=segments.len()).contains(&stats.max_depth_reached));
+    // the planar faces are closed without walking back on a segment, unlike some of the compound
+    assert_eq!(0, stats.backtracks);
+    let backtracks = polygonum::Pipeline::from(dataset!("compound.geojson"))
+        .apply(|graph| std::iter::once(polygonum::traversal::traverse_with_stats(&graph).1))
+        .iter()
+        .map(|stats| stats.backtracks)
+        .sum::<usize>();
+    assert!(backtracks > 0);
+}
+
+#[test]
+fn filter_with() {
+    let polygons = polygonum::Pipeline::from(dataset!("house.geojson")).apply(|graph| {
+        polygonum::traversal::traverse_with_stats(&graph)
+            .0
+            .into_iter()
+    });
+    let horizontal = polygonum::filter_with(polygons.clone(), 0.01, |polygon| {
+        polygon.is_horizontal(1e-3)
+    })
+    .collect::<Vec<_>>();
+    assert!(!horizontal.is_empty());
+    assert!(horizontal.iter().all(|polygon| polygon.is_horizontal(1e-3)));
+    assert_eq!(
+        polygonum::filter(polygons.clone(), 0.01).count(),
+        polygonum::filter_with(polygons, 0.01, |_| true).count()
+    );
+}
+
+#[test]
+fn deterministic_order() {
+    for dataset in [dataset!("house.geojson"), dataset!("compound.geojson")] {
+        let sequential = polygonum::polygonalize(dataset, false, 0.01);
+        assert_eq!(sequential, polygonum::polygonalize(dataset, false, 0.01));
+        assert_eq!(sequential, polygonum::polygonalize(dataset, true, 0.01));
+        assert!(sequential.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+#[test]
+fn merge_coplanar_adjacent_polygons() {
+    // four triangles fanning around the center of a square
+    let corners = [
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 0f64),
+        point!(2f64, 2f64, 0f64),
+        point!(0f64, 2f64, 0f64),
+    ];
+    let triangles = (0..4)
+        .map(|i| {
+            polygonum::Polygon::from(vec![
+                corners[i],
+                corners[(i + 1) % 4],
+                point!(1f64, 1f64, 0f64),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let merged = polygonum::merge_coplanar_adjacent_polygons(&triangles, 1e-6);
+    assert_eq!(1, merged.len());
+    assert_eq!(polygonum::Polygon::from(corners.to_vec()), merged[0]);
+    assert!((merged[0].area() - 4f64).abs() < 1e-9);
+    // the two slopes of a roof meeting at the ridge
+    let slopes = [
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 1f64),
+            point!(2f64, 0f64, 1f64),
+            point!(1f64, 1f64, 0f64),
+        ]),
+        polygonum::Polygon::from(vec![
+            point!(2f64, 0f64, 1f64),
+            point!(0f64, 0f64, 1f64),
+            point!(1f64, -1f64, 0f64),
+        ]),
+    ];
+    assert_eq!(
+        slopes.to_vec(),
+        polygonum::merge_coplanar_adjacent_polygons(&slopes, 1e-6)
+    );
+}
+
+#[test]
+fn compute_adjacency() {
+    // the faces of the unit cube
+    let corner = |i: usize| point!((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64);
+    let cube = [
+        [0, 1, 3, 2],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [0, 2, 6, 4],
+        [1, 3, 7, 5],
+    ]
+    .map(|face| polygonum::Polygon::from(face.iter().map(|&i| corner(i)).collect()));
+    let adjacency = polygonum::compute_adjacency(&cube);
+    assert_eq!(6, adjacency.len());
+    assert!(adjacency.values().all(|neighbors| neighbors.len() == 4));
+    assert_eq!(vec![2, 3, 4, 5], adjacency[&0]);
+    // two disjoint triangles
+    let triangles = [
+        polygonum::Polygon::from(vec![
+            point!(0f64, 0f64, 0f64),
+            point!(1f64, 0f64, 0f64),
+            point!(0f64, 1f64, 0f64),
+        ]),
+        polygonum::Polygon::from(vec![
+            point!(5f64, 0f64, 0f64),
+            point!(6f64, 0f64, 0f64),
+            point!(5f64, 1f64, 0f64),
+        ]),
+    ];
+    let adjacency = polygonum::compute_adjacency(&triangles);
+    assert_eq!(2, adjacency.len());
+    assert!(adjacency.values().all(Vec::is_empty));
+}
+
+#[test]
+fn export_obj() {
+    use polygonum::io::export_obj;
+
+    let polygons = polygonum::polygonalize(
+        &[
+            segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+            segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+            segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+            segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+            segment!(10f64, 10f64, 5f64 => 20f64, 10f64, 0f64),
+            segment!(20f64, 10f64, 0f64 => 20f64, 0f64, 0f64),
+            segment!(20f64, 0f64, 0f64 => 10f64, 0f64, 5f64),
+        ],
+        true,
+        0.01,
+    );
+    let mut buffer = Vec::new();
+    export_obj(&polygons, &mut buffer).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    // the shared side is written once
+    let vertices = content
+        .lines()
+        .filter_map(|line| line.strip_prefix("v "))
+        .map(|line| {
+            line.split(' ')
+                .map(|value| value.parse::<f64>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let faces = content
+        .lines()
+        .filter_map(|line| line.strip_prefix("f "))
+        .map(|line| {
+            line.split(' ')
+                .map(|index| index.parse::<usize>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(6, vertices.len());
+    assert_eq!(2, faces.len());
+    assert!(vertices
+        .iter()
+        .any(|vertex| vertex == &[10f64, 10f64, 5f64]));
+    assert!(faces
+        .iter()
+        .flatten()
+        .all(|&index| (1..=vertices.len()).contains(&index)));
+}
+
+#[test]
+fn export_stl() {
+    use polygonum::io::{export_stl_ascii, export_stl_binary};
+
+    let polygons = polygonum::polygonalize(dataset!("house.geojson"), true, 0.01);
+    assert_eq!(18, polygons.len());
+    let expected = polygons
+        .iter()
+        .map(|polygon| polygon.vertex_count() - 2)
+        .sum::<usize>();
+    // parses the binary triangles
+    let mut buffer = Vec::new();
+    export_stl_binary(&polygons, &mut buffer).unwrap();
+    let count = u32::from_le_bytes(buffer[80..84].try_into().unwrap()) as usize;
+    assert_eq!(expected, count);
+    assert_eq!(84 + 50 * count, buffer.len());
+    for facet in buffer[84..].chunks(50) {
+        let values = facet[..48]
+            .chunks(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let norm = values[..3].iter().map(|value| value * value).sum::<f32>();
+        assert!((norm - 1f32).abs() < 1e-4);
+        assert_eq!([0u8; 2], facet[48..]);
+    }
+    // the textual variant holds as many facets
+    let mut buffer = Vec::new();
+    export_stl_ascii(&polygons, &mut buffer).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        expected,
+        content
+            .lines()
+            .filter(|line| line.starts_with("facet normal"))
+            .count()
+    );
+    assert_eq!(3 * expected, content.matches("vertex ").count());
+}
+
+#[test]
+fn export_ply() {
+    use polygonum::io::export_ply;
+
+    let polygons = polygonum::polygonalize(dataset!("house.geojson"), true, 0.01);
+    let mut buffer = Vec::new();
+    export_ply(&polygons, &mut buffer).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    // parses the header
+    let mut lines = content.lines();
+    assert_eq!(Some("ply"), lines.next());
+    let header = lines
+        .by_ref()
+        .take_while(|&line| line != "end_header")
+        .collect::<Vec<_>>();
+    let count = |element: &str| {
+        header
+            .iter()
+            .find_map(|line| line.strip_prefix(element))
+            .unwrap()
+            .parse::<usize>()
+            .unwrap()
+    };
+    let (vertex_count, face_count) = (count("element vertex "), count("element face "));
+    assert!(
+        header.contains(&"property list uint uint vertex_indices"),
+        "Faces with more than 255 vertices can be declared."
+    );
+    // parses the body
+    let vertices = lines
+        .by_ref()
+        .take(vertex_count)
+        .map(|line| {
+            let values = line
+                .split(' ')
+                .map(|value| value.parse::<f64>().unwrap())
+                .collect::<Vec<_>>();
+            point!(values[0], values[1], values[2])
+        })
+        .collect::<Vec<_>>();
+    let faces = lines
+        .map(|line| {
+            line.split(' ')
+                .map(|value| value.parse::<usize>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vertex_count, vertices.len());
+    assert_eq!(polygons.len(), face_count);
+    assert_eq!(face_count, faces.len());
+    assert!(faces
+        .iter()
+        .all(|face| face[0] == face.len() - 1 && face[1..].iter().all(|&i| i < vertex_count)));
+    // the faces rebuild the polygons
+    let rebuilt = faces
+        .iter()
+        .map(|face| polygonum::Polygon::from(face[1..].iter().map(|&i| vertices[i]).collect()))
+        .collect::<Vec<_>>();
+    assert_eq!(polygons, rebuilt);
+}
+
+#[test]
+fn export_svg() {
+    use polygonum::io::{export_svg, export_svg_with_fill, SvgProjection};
+
+    let polygons = polygonum::polygonalize(
+        &[
+            segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64),
+            segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 5f64),
+            segment!(10f64, 10f64, 5f64 => 10f64, 0f64, 5f64),
+            segment!(10f64, 0f64, 5f64 => 0f64, 0f64, 0f64),
+            segment!(10f64, 10f64, 5f64 => 20f64, 10f64, 0f64),
+            segment!(20f64, 10f64, 0f64 => 20f64, 0f64, 0f64),
+            segment!(20f64, 0f64, 0f64 => 10f64, 0f64, 5f64),
+        ],
+        true,
+        0.01,
+    );
+    for projection in [SvgProjection::TopDown, SvgProjection::FrontFace] {
+        let mut buffer = Vec::new();
+        export_svg(&polygons, &mut buffer, projection).unwrap();
+        let content = String::from_utf8(buffer).unwrap();
+        let document = roxmltree::Document::parse(&content).unwrap();
+        let root = document.root_element();
+        assert_eq!("svg", root.tag_name().name());
+        let elements = root
+            .children()
+            .filter(|node| node.tag_name().name() == "polygon")
+            .collect::<Vec<_>>();
+        assert_eq!(2, elements.len());
+        // the view box contains every drawn point
+        let view = root
+            .attribute("viewBox")
+            .unwrap()
+            .split(' ')
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        for element in elements {
+            assert_eq!(Some("none"), element.attribute("fill"));
+            for point in element.attribute("points").unwrap().split(' ') {
+                let (x, y) = point.split_once(',').unwrap();
+                let (x, y) = (x.parse::<f64>().unwrap(), y.parse::<f64>().unwrap());
+                assert!(view[0] < x && x < view[0] + view[2]);
+                assert!(view[1] < y && y < view[1] + view[3]);
+            }
+        }
+    }
+    let mut buffer = Vec::new();
+    export_svg_with_fill(&polygons, &mut buffer, SvgProjection::TopDown, |_| {
+        Some("red".to_string())
+    })
+    .unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    let document = roxmltree::Document::parse(&content).unwrap();
+    assert!(document
+        .descendants()
+        .filter(|node| node.tag_name().name() == "polygon")
+        .all(|node| node.attribute("fill") == Some("red")));
+}
+
+#[test]
+fn segments_csv() {
+    use polygonum::io::{parse_segments_csv, write_segments_csv, ParseError};
+
+    let content = "# x1,y1,z1,x2,y2,z2\n0,0,0,1,0,0\n\n1,0,0,1,1,0.5\n1, 1, 0.5, 0, 0, 0\n";
+    let segments = parse_segments_csv(content.as_bytes()).unwrap();
+    assert_eq!(
+        vec![
+            segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+            segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0.5),
+            segment!(1f64, 1f64, 0.5 => 0f64, 0f64, 0f64),
+        ],
+        segments
+    );
+    // writing and reading back preserves the segments
+    let mut buffer = Vec::new();
+    write_segments_csv(&segments, &mut buffer).unwrap();
+    assert_eq!(segments, parse_segments_csv(buffer.as_slice()).unwrap());
+    // the faulty line is reported
+    let error = parse_segments_csv("0,0,0,1,0,0\n# comment\n1,0,zero,1,1,0\n".as_bytes());
+    assert!(matches!(error, Err(ParseError { line: 3, .. })));
+    assert!(error.unwrap_err().description.contains("z1"));
+    assert!(matches!(
+        parse_segments_csv("0,0,0,1,0\n".as_bytes()),
+        Err(ParseError { line: 1, .. })
+    ));
+}
+
+#[test]
+fn into_iter() {
+    let polygon = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ]);
+    let mut borrowed = Vec::new();
+    for point in &polygon {
+        borrowed.push(point);
+    }
+    assert_eq!(polygon.iter().collect::<Vec<_>>(), borrowed);
+    assert_eq!(borrowed.first(), borrowed.last());
+    assert_eq!(
+        polygon.vertex_count() + 1,
+        polygon.clone().into_iter().count()
+    );
+    assert_eq!(borrowed, polygon.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn collect_point_graph() {
+    let segments = dataset!("house.geojson");
+    let graph = segments.iter().copied().collect::<polygonum::PointGraph>();
+    assert!(graph == polygonum::PointGraph::from(segments));
+    // the graph can be constructed incrementally
+    let (head, tail) = segments.split_at(segments.len() / 2);
+    let mut extended = polygonum::PointGraph::from(head);
+    extended.extend(tail.iter().copied());
+    assert!(extended == graph);
+    // chains with other iterator adaptors
+    let squares = (0..3)
+        .flat_map(|i| {
+            let x = i as f64;
+            [
+                segment!(x, 0f64, 0f64 => x + 1f64, 0f64, 0f64),
+                segment!(x + 1f64, 0f64, 0f64 => x + 1f64, 1f64, 0f64),
+                segment!(x + 1f64, 1f64, 0f64 => x, 1f64, 0f64),
+                segment!(x, 1f64, 0f64 => x, 0f64, 0f64),
+            ]
+        })
+        .collect::<polygonum::PointGraph>();
+    assert_eq!(1, squares.connected_component_count());
+}
+
+#[test]
+fn polygon_conversions() {
+    let vertices = vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+    ];
+    let polygon = polygonum::Polygon::try_from(vertices.clone()).unwrap();
+    assert_eq!(3, polygon.vertex_count());
+    assert_eq!(
+        Ok(polygon),
+        polygonum::Polygon::try_from(vertices.as_slice())
+    );
+    // too few or collinear vertices are rejected
+    assert_eq!(
+        Err(polygonum::PolygonError::Degenerate),
+        polygonum::Polygon::try_from(&vertices[..2])
+    );
+    assert_eq!(
+        Err(polygonum::PolygonError::Degenerate),
+        polygonum::Polygon::try_from(vertices[..2].to_vec())
+    );
+    assert_eq!(
+        Err(polygonum::PolygonError::Degenerate),
+        polygonum::Polygon::try_from(
+            [
+                point!(0f64, 0f64, 0f64),
+                point!(1f64, 1f64, 1f64),
+                point!(2f64, 2f64, 2f64),
+            ]
+            .as_slice()
+        )
+    );
+}
+
+#[test]
+fn deduplicate_segments() {
+    let square = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 1f64, 0f64),
+        segment!(0f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+    ];
+    let twice = square
+        .iter()
+        .chain(square.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    let unique = polygonum::deduplicate_segments(&twice);
+    assert_eq!(4, unique.len());
+    assert!(unique
+        .iter()
+        .zip(&square)
+        .all(|(&a, &b)| polygonum::segment_canonical_eq(a, b)));
+    // a segment and its reverse collapse into the canonical one
+    let (a, b) = (point!(1f64, 2f64, 3f64), point!(0f64, 5f64, 1f64));
+    assert_eq!(
+        vec![(b, a)],
+        polygonum::deduplicate_segments(&[(a, b), (b, a)])
+    );
+}
+
+#[test]
+fn validate_segments() {
+    use polygonum::SegmentError;
+
+    let valid = segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64);
+    let nan = segment!(0f64, f64::NAN, 0f64 => 1f64, 0f64, 0f64);
+    let infinite = segment!(0f64, 0f64, 0f64 => f64::INFINITY, 0f64, 0f64);
+    let zero = segment!(1f64, 2f64, 3f64 => 1f64, 2f64, 3f64);
+    assert_eq!(Ok(()), polygonum::validate_segments(&[valid]));
+    assert_eq!(
+        Ok(()),
+        polygonum::validate_segments(dataset!("house.geojson"))
+    );
+    let errors = polygonum::validate_segments(&[valid, nan, zero, infinite]).unwrap_err();
+    assert_eq!(3, errors.len());
+    assert!(matches!(errors[0], SegmentError::NonFinite(_)));
+    assert_eq!(SegmentError::ZeroLength(zero), errors[1]);
+    assert_eq!(SegmentError::NonFinite(infinite), errors[2]);
+}
+
+#[test]
+fn is_degenerate() {
+    let collinear = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 1f64, 1f64),
+        point!(2f64, 2f64, 2f64),
+    ]);
+    assert!(collinear.is_degenerate());
+    assert!(collinear.triangulate().is_empty());
+    assert_eq!(0f64, collinear.normal().norm());
+    // small but valid
+    let tiny = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2e-8, 0f64, 0f64),
+        point!(0f64, 1e-7, 0f64),
+    ]);
+    assert!((tiny.area() - 1e-15).abs() < 1e-20);
+    assert!(!tiny.is_degenerate());
+    assert_eq!(1, tiny.triangulate().len());
+    assert!(
+        polygonum::polygonalize(dataset!("house.geojson"), true, 0.01)
+            .iter()
+            .all(|polygon| !polygon.is_degenerate())
+    );
+}
+
+#[test]
+fn robust_orientation() {
+    use polygonum::plane::robust_orientation;
+
+    let (a, b, c, d) = (
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+        point!(0f64, 0f64, 1f64),
+    );
+    assert_eq!(1f64, robust_orientation(a, b, c, d));
+    assert_eq!(-1f64, robust_orientation(b, a, c, d));
+    assert_eq!(0f64, robust_orientation(a, b, c, point!(5f64, -3f64, 0f64)));
+    // nearly coplanar points with large coordinates, whose exact orientation is computed on integers
+    let exact = |points: [[i64; 3]; 4]| {
+        let [a, b, c, d] = points.map(|p| p.map(i128::from));
+        let (u, v, w) = (
+            [b[0] - a[0], b[1] - a[1], b[2] - a[2]],
+            [c[0] - a[0], c[1] - a[1], c[2] - a[2]],
+            [d[0] - a[0], d[1] - a[1], d[2] - a[2]],
+        );
+        (u[1] * v[2] - u[2] * v[1]) * w[0]
+            + (u[2] * v[0] - u[0] * v[2]) * w[1]
+            + (u[0] * v[1] - u[1] * v[0]) * w[2]
+    };
+    let mut state = 12345u64;
+    let mut random = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % (1 << 24)) as i64
+    };
+    for _ in 0..2000 {
+        // `d` lies almost on the plane through `a`, `b` and `c`
+        let (a, b, c) = (
+            [random(), random(), random()],
+            [random(), random(), random()],
+            [random(), random(), random()],
+        );
+        let d = [
+            a[0] + b[0] - c[0],
+            a[1] + b[1] - c[1],
+            a[2] + b[2] - c[2] + random() % 3 - 1,
+        ];
+        let expected = exact([a, b, c, d]).signum();
+        let [a, b, c, d] = [a, b, c, d].map(|p| point!(p[0] as f64, p[1] as f64, p[2] as f64));
+        let orientation = robust_orientation(a, b, c, d);
+        assert_eq!(
+            expected,
+            (orientation > 0f64) as i128 - (orientation < 0f64) as i128
+        );
+    }
+}
+
+#[test]
+fn offset() {
+    let shift = |segments: &[polygonum::Segment], (x, y, z): (f64, f64, f64)| {
+        segments
+            .iter()
+            .map(|(from, to)| {
+                (
+                    point!(from.x + x, from.y + y, from.z + z),
+                    point!(to.x + x, to.y + y, to.z + z),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    // small coordinates are left unchanged
+    let square = [
+        segment!(0f64, 0f64, 0f64 => 1f64, 0f64, 0f64),
+        segment!(1f64, 0f64, 0f64 => 1f64, 1f64, 0f64),
+        segment!(1f64, 1f64, 0f64 => 0f64, 0f64, 0f64),
+    ];
+    assert!(polygonum::Pipeline::from(&square).offset().is_identity());
+    // the same structure close to the origin and in UTM coordinates
+    let local = shift(dataset!("house.geojson"), (-297675f64, -6700765f64, 0f64));
+    let utm = shift(&local, (600000f64, 5000000f64, 0f64));
+    let pipeline = polygonum::Pipeline::from(&utm);
+    assert!(!pipeline.offset().is_identity());
+    assert_eq!(0f64, pipeline.offset().centroid().x % 1024f64);
+    let polygons = polygonum::polygonalize(&utm, true, 0.01);
+    let expected = polygonum::polygonalize(&local, true, 0.01);
+    assert_eq!(18, expected.len());
+    assert_eq!(expected.len(), polygons.len());
+    // the polygons are restored in the original coordinates
+    let endpoints = utm
+        .iter()
+        .flat_map(|&(from, to)| [from, to])
+        .collect::<std::collections::HashSet<_>>();
+    assert!(polygons
+        .iter()
+        .all(|polygon| polygon.iter().all(|point| endpoints.contains(&point))));
+    let areas = |polygons: &[polygonum::Polygon]| {
+        let mut areas = polygons
+            .iter()
+            .map(|polygon| polygon.area())
+            .collect::<Vec<_>>();
+        areas.sort_by(f64::total_cmp);
+        areas
+    };
+    assert!(areas(&polygons)
+        .iter()
+        .zip(areas(&expected))
+        .all(|(alpha, beta)| (alpha - beta).abs() < 1e-6));
+    // a tiny triangle whose vertices would be merged by the translation far from it
+    let mixed = [
+        segment!(0f64, 0f64, 0f64 => 1e-12, 0f64, 0f64),
+        segment!(1e-12, 0f64, 0f64 => 0f64, 1e-12, 0f64),
+        segment!(0f64, 1e-12, 0f64 => 0f64, 0f64, 0f64),
+    ]
+    .into_iter()
+    .chain(shift(&square, (1e6, 1e6, 0f64)))
+    .chain(shift(&square, (2e6, 2e6, 0f64)))
+    .collect::<Vec<_>>();
+    assert!(polygonum::Pipeline::from(&mixed).offset().is_identity());
+    assert_eq!(3, polygonum::polygonalize(&mixed, false, 0f64).len());
+}
+
+#[test]
+fn newell_normal() {
+    let square = |(x, y): (f64, f64)| {
+        polygonum::Polygon::from(vec![
+            point!(x, y, 0f64),
+            point!(x + 1f64, y, 0f64),
+            point!(x + 1f64, y + 1f64, 0f64),
+            point!(x, y + 1f64, 0f64),
+        ])
+    };
+    let expected = polygonum::Vector {
+        x: 0f64,
+        y: 0f64,
+        z: 2f64,
+    };
+    let normal = square((0f64, 0f64)).normal();
+    assert!((normal - expected).norm() < 1e-10);
+    // far from the origin the normal stays accurate
+    let normal = square((1e6, 1e6)).normal();
+    assert!((normal - expected).norm() < 1e-6);
+    // a slanted polygon far from the origin agrees with the same one at the origin
+    let slanted = |offset: f64| {
+        polygonum::Polygon::from(vec![
+            point!(offset, offset, 0f64),
+            point!(offset + 3f64, offset, 1f64),
+            point!(offset + 3f64, offset + 2f64, 2f64),
+            point!(offset + 1f64, offset + 3f64, 1.5),
+        ])
+    };
+    assert!((slanted(1e6).normal() - slanted(0f64).normal()).norm() < 1e-6);
+}
+
+#[test]
+fn from_with_tolerance() {
+    // two squares sharing a corner given with a tiny discrepancy
+    let square = |[a, b, c, d]: [polygonum::Point; 4]| [(a, b), (b, c), (c, d), (d, a)];
+    let segments = square([
+        point!(0f64, 0f64, 0f64),
+        point!(1f64, 0f64, 0f64),
+        point!(1f64, 1f64, 0f64),
+        point!(0f64, 1f64, 0f64),
+    ])
+    .into_iter()
+    .chain(square([
+        point!(1f64 + 1e-12, 1f64 - 1e-12, 0f64),
+        point!(2f64, 1f64, 0f64),
+        point!(2f64, 2f64, 0f64),
+        point!(1f64, 2f64, 0f64),
+    ]))
+    .collect::<Vec<_>>();
+    assert_eq!(
+        2,
+        polygonum::PointGraph::from(&segments).connected_component_count()
+    );
+    let graph = polygonum::PointGraph::from_with_tolerance(&segments, 1e-9);
+    assert_eq!(1, graph.connected_component_count());
+    assert!(graph == polygonum::PointGraph::from_with_tolerance(&segments, 1e-9));
+    assert!(
+        polygonum::PointGraph::from_with_tolerance(&segments, 0f64)
+            == polygonum::PointGraph::from(&segments)
+    );
+    // large coordinates with respect to the tolerance do not overflow the grid of close points
+    let triangle = [
+        segment!(1e7, 0f64, 0f64 => 1e7 + 1f64, 0f64, 0f64),
+        segment!(1e7 + 1f64, 0f64, 0f64 => 1e7, 1f64, 0f64),
+        segment!(1e7, 1f64, 0f64 => 1e7, 0f64, 0f64),
+    ];
+    assert!(
+        polygonum::PointGraph::from_with_tolerance(&triangle, 1e-12)
+            == polygonum::PointGraph::from(&triangle)
+    );
+}
+
+#[test]
+fn convex_hull() {
+    // a convex quadrilateral on a slanted plane
+    let convex = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(4f64, 0f64, 2f64),
+        point!(5f64, 3f64, 2.5),
+        point!(1f64, 4f64, 0.5),
+    ]);
+    let hull = convex.convex_hull();
+    assert_eq!(convex, hull);
+    assert!(hull.normal().z > 0f64);
+    assert_eq!(hull.iter().next(), hull.iter().last());
+    // an L-shape on a vertical plane loses its reflex vertex
+    let shape = polygonum::Polygon::from(vec![
+        point!(0f64, 0f64, 0f64),
+        point!(2f64, 0f64, 0f64),
+        point!(2f64, 0f64, 1f64),
+        point!(1f64, 0f64, 1f64),
+        point!(1f64, 0f64, 2f64),
+        point!(0f64, 0f64, 2f64),
+    ]);
+    let hull = shape.convex_hull();
+    assert_eq!(5, hull.vertex_count());
+    assert!(hull.vertex_count() < shape.vertex_count());
+    assert!(!hull.iter().any(|point| point == point!(1f64, 0f64, 1f64)));
+    assert!((hull.area() - 3.5).abs() < 1e-9);
+    assert!(hull.is_convex());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_degenerate_polygon() {
+    assert!(
+        serde_json::from_str::<polygonum::Polygon>(r#"{"vertices":[]}"#).is_err(),
+        "A polygon without any vertex is rejected."
+    );
+    assert!(
+        serde_json::from_str::<polygonum::Polygon>(
+            r#"{"vertices":[{"x":0.0,"y":0.0,"z":0.0},{"x":1.0,"y":0.0,"z":0.0}]}"#
+        )
+        .is_err(),
+        "A polygon with two vertices is rejected."
+    );
+    assert!(
+        serde_json::from_str::<polygonum::Polygon>(
+            r#"{"vertices":[{"x":0.0,"y":0.0,"z":0.0},{"x":1.0,"y":0.0,"z":0.0},{"x":2.0,"y":0.0,"z":0.0}]}"#
+        )
+        .is_err(),
+        "A polygon with collinear vertices is rejected."
+    );
+}
+
+#[test]
+fn weighted_zero() {
+    assert_eq!(
+        Err(polygonum::GraphError::InvalidWeight(
+            point!(0f64, 10f64, 0f64),
+            point!(10f64, 10f64, 0f64),
+            0f32
+        )),
+        polygonum::polygonalize_weighted(
+            &[
+                (segment!(0f64, 0f64, 0f64 => 0f64, 10f64, 0f64), 1f32),
+                (segment!(0f64, 10f64, 0f64 => 10f64, 10f64, 0f64), 0f32),
+                (segment!(10f64, 10f64, 0f64 => 0f64, 0f64, 0f64), 1f32),
+            ],
+            false,
+            0f64,
+        )
+    );
+}
+
+#[test]
+fn polygonalize_with_progress_offset() {
+    // the house translated in UTM coordinates, far from its own ones
+    let utm = dataset!("house.geojson")
+        .iter()
+        .map(|(from, to)| {
+            (
+                point!(
+                    from.x - 297675f64 + 600000f64,
+                    from.y - 6700765f64 + 5000000f64,
+                    from.z
+                ),
+                point!(
+                    to.x - 297675f64 + 600000f64,
+                    to.y - 6700765f64 + 5000000f64,
+                    to.z
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    for parallelize in [false, true] {
+        assert_eq!(
+            polygonum::polygonalize(&utm, parallelize, 0.01),
+            polygonum::polygonalize_with_progress(&utm, parallelize, 0.01, |_| {}),
+            "The polygons are restored in the original coordinates."
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "must be finite and positive")]
+fn snap_to_grid_zero() {
+    polygonum::snap_to_grid(
+        polygonum::polygonalize(dataset!("house.geojson"), true, 0.01),
+        0f64,
+    );
+}
+
 mod io {
     pub(super) fn parse(filename: &str) -> Vec<polygonum::Segment> {
         match std::fs::read_to_string(filename) {